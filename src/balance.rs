@@ -0,0 +1,326 @@
+use crate::{
+    errors::{ERR_INSUFFICIENT_BALANCE, ERR_INVALID_DEPOSIT_TOKEN, ERR_NOTHING_TO_DEPOSIT, ERR_NOTHING_TO_WITHDRAW},
+    escrow,
+    types::{Bet, BetStatus, BetType}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait BalanceModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::limits::LimitsModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Deposits --------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Deposits the attached payment into the caller's free balance, so bets can later be placed
+    /// from it without attaching a transfer to every order.
+    #[payable("*")]
+    #[endpoint(deposit)]
+    fn deposit(&self) {
+        let caller = self.blockchain().get_caller();
+        let (token, nonce, amount) = self
+            .call_value()
+            .egld_or_single_esdt()
+            .into_tuple();
+        require!(nonce == 0, ERR_INVALID_DEPOSIT_TOKEN);
+        require!(amount > BigUint::zero(), ERR_NOTHING_TO_DEPOSIT);
+        self.enforce_deposit_limits(&caller, &token, &amount);
+
+        self.credit_balance(&caller, &token, &amount);
+        self.deposit_event(&caller, &token, &amount);
+    }
+
+    /// Credits every payment attached to the current call to the caller's free balance.
+    /// Accepts either EGLD or any number of fungible ESDT transfers.
+    fn deposit_attached_payments(&self, caller: &ManagedAddress) {
+        let egld_amount = self.call_value().egld_value().clone_value();
+        if egld_amount > BigUint::zero() {
+            let token = EgldOrEsdtTokenIdentifier::egld();
+            self.enforce_deposit_limits(caller, &token, &egld_amount);
+            self.credit_balance(caller, &token, &egld_amount);
+            self.deposit_event(caller, &token, &egld_amount);
+        }
+
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+        for payment in payments.iter() {
+            require!(payment.token_nonce == 0, ERR_INVALID_DEPOSIT_TOKEN);
+            let token = EgldOrEsdtTokenIdentifier::esdt(payment.token_identifier.clone());
+            self.enforce_deposit_limits(caller, &token, &payment.amount);
+            self.credit_balance(caller, &token, &payment.amount);
+            self.deposit_event(caller, &token, &payment.amount);
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Withdrawals -----------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Withdraws the caller's full free balance of a token.
+    /// Parameters:
+    /// - token: The token to withdraw.
+    #[endpoint(withdraw)]
+    fn withdraw(&self, token: EgldOrEsdtTokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        let amount = self.take_claimable_balance(&caller, &token);
+        require!(amount > BigUint::zero(), ERR_NOTHING_TO_WITHDRAW);
+
+        self.send().direct(&caller, &token, 0, &amount);
+        self.withdraw_event(&caller, &token, &amount);
+    }
+
+    /// Withdraws the caller's free balances of every token in one call.
+    #[endpoint(withdrawAll)]
+    fn withdraw_all(&self) {
+        let caller = self.blockchain().get_caller();
+        let tokens = self.claimable_tokens(&caller).iter().collect::<ManagedVec<EgldOrEsdtTokenIdentifier>>();
+        require!(!tokens.is_empty(), ERR_NOTHING_TO_WITHDRAW);
+
+        for token in tokens.iter() {
+            let amount = self.take_claimable_balance(&caller, &token);
+            if amount > BigUint::zero() {
+                self.send().direct(&caller, &token, 0, &amount);
+                self.withdraw_event(&caller, &token, &amount);
+            }
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Credits an amount to an address' free balance instead of transferring it.
+    /// Parameters:
+    /// - address: The address being credited.
+    /// - token: The token being credited.
+    /// - amount: The amount to credit.
+    fn credit_balance(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        if *amount == BigUint::zero() {
+            return;
+        }
+
+        self.claimable_balance(address, token).update(|balance| *balance += amount);
+        self.claimable_tokens(address).insert(token.clone());
+        self.total_claimable_balance(token).update(|total| *total += amount);
+    }
+
+    /// Debits an amount from an address' free balance, failing if the balance is too low.
+    /// Parameters:
+    /// - address: The address being debited.
+    /// - token: The token being debited.
+    /// - amount: The amount to debit.
+    fn debit_balance(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        let balance = self.claimable_balance(address, token).get();
+        require!(balance >= *amount, ERR_INSUFFICIENT_BALANCE);
+
+        self.reduce_total_claimable(token, amount);
+        let remaining = balance - amount;
+        if remaining == BigUint::zero() {
+            self.claimable_balance(address, token).clear();
+            self.claimable_tokens(address).swap_remove(token);
+        } else {
+            self.claimable_balance(address, token).set(&remaining);
+        }
+    }
+
+    /// Adds an amount to the funds an address has locked in unmatched bets.
+    /// Parameters:
+    /// - address: The bettor.
+    /// - token: The token of the bet.
+    /// - amount: The amount to lock.
+    fn lock_funds(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        if *amount > BigUint::zero() {
+            self.locked_funds(address, token).update(|funds| *funds += amount);
+            self.total_locked_funds(token).update(|total| *total += amount);
+        }
+    }
+
+    /// Releases an amount from the funds an address has locked in unmatched bets.
+    /// Parameters:
+    /// - address: The bettor.
+    /// - token: The token of the bet.
+    /// - amount: The amount to release.
+    fn unlock_funds(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        let released = self.locked_funds(address, token).update(|funds| {
+            if *funds >= *amount {
+                *funds -= amount;
+                amount.clone()
+            } else {
+                core::mem::replace(funds, BigUint::zero())
+            }
+        });
+        self.total_locked_funds(token).update(|total| {
+            if *total >= released {
+                *total -= &released;
+            } else {
+                *total = BigUint::zero();
+            }
+        });
+    }
+
+    /// Releases the escrow of a bet's unmatched remainder: unlocks what was locked for it, credits the
+    /// refund to the bettor's free balance and keeps the rounding difference as escrow dust.
+    /// The bet itself is not modified.
+    /// Parameters:
+    /// - bet: The bet whose remainder is refunded.
+    /// - unmatched: The unmatched part of the stake.
+    /// Returns: A tuple of (refund, retained), retained being the escrow left for the matched part.
+    fn refund_unmatched_escrow(&self, bet: &Bet<Self::Api>, unmatched: &BigUint) -> (BigUint, BigUint) {
+        let (refund, retained, dust) = escrow::split_unmatched(bet.bet_type, &bet.total_amount, &bet.stake_amount, unmatched);
+
+        // What the matched part can still lose stays reserved against the loss limits
+        let retained_loss = match bet.bet_type {
+            BetType::Back => &bet.stake_amount - unmatched,
+            BetType::Lay => &retained - &bet.total_matched,
+        };
+        let max_loss = self.bet_max_loss(bet);
+        if max_loss > retained_loss {
+            self.release_max_loss(&bet.bettor, &bet.payment_token, &(max_loss - retained_loss));
+        }
+
+        self.unlock_funds(&bet.bettor, &bet.payment_token, &(&refund + &dust));
+        self.credit_balance(&bet.bettor, &bet.payment_token, &refund);
+        if dust > BigUint::zero() {
+            self.escrow_dust(&bet.payment_token).update(|total| *total += &dust);
+        }
+        (refund, retained)
+    }
+
+    /// Moves the escrow of a resting bet placed before locked funds were kept per token from the
+    /// legacy address-wide entry to the entry of the bet's token, and indexes the bet as escrowed.
+    /// Resting bets placed since are always indexed in user_escrowed_bets, so a resting bet missing
+    /// from it still has its escrow in the legacy entry. Does nothing for any other bet.
+    /// Parameters:
+    /// - bet: The bet about to be matched, cancelled, refunded or migrated.
+    fn fold_legacy_escrow(&self, bet: &Bet<Self::Api>) {
+        let resting = (bet.status == BetStatus::Unmatched || bet.status == BetStatus::PartiallyMatched)
+            && bet.total_matched < bet.stake_amount;
+        if !resting || self.user_escrowed_bets(&bet.bettor).contains(&bet.bet_id) {
+            return;
+        }
+
+        let unmatched = &bet.stake_amount - &bet.total_matched;
+        let escrow = escrow::locked_share(bet.bet_type, &bet.total_amount, &bet.stake_amount, &unmatched);
+        self.legacy_locked_funds(&bet.bettor).update(|funds| {
+            if *funds >= escrow {
+                *funds -= &escrow;
+            } else {
+                *funds = BigUint::zero();
+            }
+        });
+        self.lock_funds(&bet.bettor, &bet.payment_token, &escrow);
+        self.user_escrowed_bets(&bet.bettor).insert(bet.bet_id);
+    }
+
+    /// Clears an address' free balance of a token and returns it.
+    /// Parameters:
+    /// - address: The address whose balance is taken.
+    /// - token: The token to take.
+    /// Returns: The amount that was claimable.
+    fn take_claimable_balance(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+    ) -> BigUint {
+        let amount = self.claimable_balance(address, token).take();
+        self.claimable_tokens(address).swap_remove(token);
+        self.reduce_total_claimable(token, &amount);
+        amount
+    }
+
+    /// Takes an amount out of the sum of all free balances of a token. The sum only covers balances
+    /// credited since it was introduced, so it saturates at zero instead of failing.
+    /// Parameters:
+    /// - token: The token.
+    /// - amount: The amount leaving the free balances.
+    fn reduce_total_claimable(&self, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        self.total_claimable_balance(token).update(|total| {
+            if *total >= *amount {
+                *total -= amount;
+            } else {
+                *total = BigUint::zero();
+            }
+        });
+    }
+
+    /// Adds an amount to the escrow held for matched bets, when a part of a bet gets matched.
+    /// Parameters:
+    /// - token: The token of the bet.
+    /// - amount: The escrow that now backs matched stakes.
+    fn add_matched_escrow(&self, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        if *amount > BigUint::zero() {
+            self.total_matched_escrow(token).update(|total| *total += amount);
+        }
+    }
+
+    /// Takes an amount paid out to a bettor (winnings or a void refund) out of the escrow held for
+    /// matched bets. Bets matched before the total was introduced are not in it, so it saturates at zero.
+    /// Parameters:
+    /// - token: The token of the bet.
+    /// - amount: The amount leaving the matched escrow.
+    fn reduce_matched_escrow(&self, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        self.total_matched_escrow(token).update(|total| {
+            if *total >= *amount {
+                *total -= amount;
+            } else {
+                *total = BigUint::zero();
+            }
+        });
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- View Functions --------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(getClaimableBalance)]
+    fn get_claimable_balance(&self, address: ManagedAddress, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.claimable_balance(&address, &token).get()
+    }
+
+    #[view(getLockedFunds)]
+    fn get_locked_funds(&self, address: ManagedAddress, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.locked_funds(&address, &token).get()
+    }
+
+    /// Returns the rounding remainders of escrow refunds kept by the protocol for a token, until swept into the house pool.
+    #[view(getEscrowDust)]
+    fn get_escrow_dust(&self, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.escrow_dust(&token).get()
+    }
+
+    #[view(getClaimableBalances)]
+    fn get_claimable_balances(
+        &self,
+        address: ManagedAddress,
+    ) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+        for token in self.claimable_tokens(&address).iter() {
+            let amount = self.claimable_balance(&address, &token).get();
+            result.push((token, amount).into());
+        }
+        result
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{errors::{ERR_BET_CANNOT_BE_CANCELLED, ERR_INVALID_LIABILITY, ERR_INVALID_STAKE, ERR_NOT_BET_OWNER, ERR_ODDS_TOO_LOW}, types::{Bet, BetStatus, BetType, Sport}};
+use crate::{constants::constants::STORAGE_VERSION, escrow, errors::{ERR_BET_CANNOT_BE_CANCELLED, ERR_BET_HAS_NFT, ERR_INSUFFICIENT_LOCKED_FUNDS, ERR_INVALID_DEPOSIT_TOKEN, ERR_INVALID_LIABILITY, ERR_INVALID_BET_ID, ERR_INVALID_MARKET, ERR_INVALID_STAKE, ERR_MARKET_NOT_MIGRATED, ERR_MARKET_NOT_OPEN, ERR_MARKET_SUSPENDED, ERR_NO_ORDERS, ERR_NOT_BET_OWNER, ERR_ODDS_TOO_LOW}, types::{sport_index, Bet, BetStatus, BetView, BetType, MarketStatus, SportId}};
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
@@ -6,6 +6,10 @@ multiversx_sc::derive_imports!();
 pub trait BetModule: 
     crate::storage::StorageModule +
     crate::events::EventsModule +
+    crate::roles::RolesModule +
+    crate::balance::BalanceModule +
+    crate::limits::LimitsModule +
+    crate::pause::PauseModule +
     crate::nft::NftModule +
     crate::orderbook::OrderbookModule +
     crate::validation::ValidationModule +
@@ -17,7 +21,7 @@ pub trait BetModule:
 
     /// Places a bet on a specified market and selection.
     /// Parameters:
-    /// - sport: The type of sport for the bet (e.g., Football, Basketball).
+    /// - sport: The sport ID of the bet (e.g., 0 for Football).
     /// - market_id: The ID of the market the bet is placed on.
     /// - selection_id: The ID of the selection within the market.
     /// - odds: The odds at which the bet is placed (in BigUint format).
@@ -26,7 +30,7 @@ pub trait BetModule:
     #[endpoint(placeBet)]
     fn place_bet(
         &self,
-        sport: Sport,
+        sport: SportId,
         market_id: u64,
         selection_id: u64,
         odds: BigUint,
@@ -37,20 +41,161 @@ pub trait BetModule:
             .call_value()
             .egld_or_single_esdt()
             .into_tuple();
-        
+        require!(token_nonce == 0, ERR_INVALID_DEPOSIT_TOKEN);
+        self.enforce_deposit_limits(&caller, &token_identifier, &total_amount);
+
+        self.execute_bet(
+            &caller,
+            sport,
+            market_id,
+            selection_id,
+            odds,
+            bet_type,
+            token_identifier,
+            token_nonce,
+            total_amount,
+            true,
+            0
+        );
+    }
+
+    /// Places a bet funded from the caller's free balance instead of an attached payment.
+    /// Parameters:
+    /// - sport: The sport ID of the bet (e.g., 0 for Football).
+    /// - market_id: The ID of the market the bet is placed on.
+    /// - selection_id: The ID of the selection within the market.
+    /// - odds: The odds at which the bet is placed (in BigUint format).
+    /// - bet_type: The type of bet (Back or Lay).
+    /// - token: The token to debit from the caller's balance.
+    /// - amount: The total amount to debit for the bet.
+    #[endpoint(placeBetFromBalance)]
+    fn place_bet_from_balance(
+        &self,
+        sport: SportId,
+        market_id: u64,
+        selection_id: u64,
+        odds: BigUint,
+        bet_type: BetType,
+        token: EgldOrEsdtTokenIdentifier,
+        amount: BigUint,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.debit_balance(&caller, &token, &amount);
+
+        self.execute_bet(
+            &caller,
+            sport,
+            market_id,
+            selection_id,
+            odds,
+            bet_type,
+            token,
+            0,
+            amount,
+            true,
+            0
+        );
+    }
+
+    /// Places several orders in one transaction, funded from the caller's free balance.
+    /// Any payments attached to the call (EGLD or multiple fungible ESDTs) are deposited first.
+    /// No betslip NFTs are minted; the orders are managed by ID through cancelOrders / cancelAllOrders
+    /// and winnings are credited to the balance on settlement.
+    /// Parameters:
+    /// - token: The token funding all orders.
+    /// - orders: List of (market_id, selection_id, odds, amount, bet_type), where amount is the
+    ///   total amount funding the order, as in placeBet.
+    /// Returns: The IDs of the placed orders, in the same order.
+    #[payable("*")]
+    #[endpoint(placeOrders)]
+    fn place_orders(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+        orders: MultiValueEncoded<MultiValue5<u64, u64, BigUint, BigUint, BetType>>,
+    ) -> MultiValueEncoded<u64> {
+        require!(!orders.is_empty(), ERR_NO_ORDERS);
+        let caller = self.blockchain().get_caller();
+        self.deposit_attached_payments(&caller);
+
+        let mut bet_ids = MultiValueEncoded::new();
+        for (order_index, order) in orders.into_iter().enumerate() {
+            let (market_id, selection_id, odds, amount, bet_type) = order.into_tuple();
+            let sport = self.require_market_sport(market_id);
+
+            self.debit_balance(&caller, &token, &amount);
+            let bet_id = self.execute_bet(
+                &caller,
+                sport,
+                market_id,
+                selection_id,
+                odds,
+                bet_type,
+                token.clone(),
+                0,
+                amount,
+                false,
+                order_index as u64
+            );
+            bet_ids.push(bet_id);
+        }
+
+        bet_ids
+    }
+
+    /// Validates, matches and records a bet whose funds are already held by the contract.
+    /// Markets created before the upgrade only take bets once migrateBatch has rewritten them.
+    /// Parameters:
+    /// - caller: The address of the bettor.
+    /// - sport: The type of sport for the bet.
+    /// - market_id: The ID of the market the bet is placed on.
+    /// - selection_id: The ID of the selection within the market.
+    /// - odds: The odds at which the bet is placed.
+    /// - bet_type: The type of bet (Back or Lay).
+    /// - token_identifier: The token the bet is funded with.
+    /// - token_nonce: The nonce of the token.
+    /// - total_amount: The total amount funding the bet.
+    /// - mint_nft: Whether a betslip NFT is minted for the bet. Bets without one are managed by ID.
+    /// - order_index: The position of the bet among the orders of the call, so that identical orders
+    ///   of one batch get distinct hashes.
+    /// Returns: The ID of the new bet.
+    fn execute_bet(
+        &self,
+        caller: &ManagedAddress,
+        sport: SportId,
+        market_id: u64,
+        selection_id: u64,
+        odds: BigUint,
+        bet_type: BetType,
+        token_identifier: EgldOrEsdtTokenIdentifier,
+        token_nonce: u64,
+        total_amount: BigUint,
+        mint_nft: bool,
+        order_index: u64,
+    ) -> u64 {
         self.validate_bet_amount(&total_amount);
         self.validate_bet_odds(&odds);
         self.validate_market(market_id);
+        require!(
+            self.market_storage_version(market_id).get() >= STORAGE_VERSION,
+            ERR_MARKET_NOT_MIGRATED
+        );
+        self.require_not_paused(market_id);
         self.validate_selection(market_id, selection_id);
+        self.validate_odds_deviation(market_id, selection_id, &odds);
         
         let (final_stake, final_liability) = self.calculate_stake_and_liability(
             &bet_type,
             &total_amount,
             &odds
         );
+        let max_loss = match bet_type {
+            BetType::Back => final_stake.clone(),
+            BetType::Lay => final_liability.clone(),
+        };
+        self.enforce_bet_limits(caller, &token_identifier, &total_amount, &max_loss);
         
         let bet_hash = self.generate_unique_bet_hash(
-            &caller,
+            caller,
             &sport,
             &market_id,
             &selection_id,
@@ -58,16 +203,17 @@ pub trait BetModule:
             &bet_type,
             &token_identifier,
             token_nonce,
-            &total_amount
+            &total_amount,
+            order_index
         );
         
-        let bet_id = self.get_bet_id_hash(&bet_hash);
+        let bet_id = self.allocate_bet_id(&bet_hash);
         
         let bet = self.create_bet(
             sport,
             market_id,
             selection_id,
-            &caller,
+            caller,
             &final_stake,
             &final_liability,
             &total_amount,
@@ -78,43 +224,54 @@ pub trait BetModule:
             bet_id
         );
         
-        let (updated_bet, matched_amount, remaining) = self.process_bet(bet);
-        self.bet_by_id(bet_id).set(&updated_bet);
-        let final_bet = self.update_bet_status(updated_bet, matched_amount.clone(), remaining.clone());
-        self.update_market_and_selection(
-            market_id,
-            selection_id,
-            &matched_amount
-        );
+        let (final_bet, matched_amount, remaining) = if self.is_bet_delay_active(market_id) {
+            let mut delayed_bet = bet;
+            delayed_bet.status = BetStatus::Pending;
+            self.bet_by_id(bet_id).set(&delayed_bet);
+            self.delayed_bets(market_id).push_back(bet_id);
+            self.user_escrowed_bets(caller).insert(bet_id);
+
+            let remaining = delayed_bet.stake_amount.clone();
+            (delayed_bet, BigUint::zero(), remaining)
+        } else {
+            let (updated_bet, matched_amount, remaining) = self.process_bet(bet);
+            self.bet_by_id(bet_id).set(&updated_bet);
+            let final_bet = self.update_bet_status(updated_bet, matched_amount.clone(), remaining.clone());
+            self.update_market_total_matched(market_id, &matched_amount);
+            self.check_circuit_breaker(market_id, selection_id, &final_bet.odd, &matched_amount);
+            (final_bet, matched_amount, remaining)
+        };
         
-        let real_nft_nonce = self.mint_bet_nft(&final_bet);
+        let real_nft_nonce = if mint_nft {
+            self.mint_bet_nft(&final_bet)
+        } else {
+            0u64
+        };
         
         let mut final_bet_with_nonce = final_bet.clone();
         final_bet_with_nonce.nft_nonce = real_nft_nonce;
         self.bet_by_id(bet_id).set(&final_bet_with_nonce);
         
-        self.bet_nonce_to_id(real_nft_nonce).set(bet_id);
+        if mint_nft {
+            self.bet_nonce_to_id(real_nft_nonce).set(bet_id);
+        } else if remaining > BigUint::zero() {
+            self.user_open_orders(caller, market_id).insert(bet_id);
+        }
         
         self.market_bet_ids(market_id).insert(bet_id);
         
-        let amount_to_lock = match bet_type {
-            BetType::Back => remaining.clone(),
-            BetType::Lay => {
-                let ratio = &remaining.clone() * &BigUint::from(100u64) / &final_bet.stake_amount;
-                (&final_bet.total_amount * &ratio) / &BigUint::from(100u64)
-            }
-        };
+        let amount_to_lock = self.calculate_amount_to_lock(&final_bet, &remaining);
+        self.lock_funds(caller, &token_identifier, &amount_to_lock);
+        self.add_matched_escrow(&token_identifier, &(&total_amount - &amount_to_lock));
     
-        if amount_to_lock > BigUint::zero() {
-            self.locked_funds(&caller).update(|funds| *funds += &amount_to_lock);
+        if mint_nft {
+            self.send().direct_esdt(
+                caller,
+                self.bet_nft_token().get_token_id_ref(),
+                real_nft_nonce,
+                &BigUint::from(1u64)
+            );
         }
-    
-        self.send().direct_esdt(
-            &caller,
-            self.bet_nft_token().get_token_id_ref(),
-            real_nft_nonce,
-            &BigUint::from(1u64)
-        );
         
         self.emit_bet_placed_event(
             &final_bet_with_nonce,
@@ -125,6 +282,100 @@ pub trait BetModule:
             real_nft_nonce,
             bet_id
         );
+
+        bet_id
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- In-Play Bet Delay -----------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Matches in-play bets whose delay has elapsed, in the order they were placed.
+    /// While the market is suspended, the waiting bets are rejected instead: their funds are returned
+    /// to the bettors' balances rather than staying locked until the market resumes or closes.
+    /// Callable by anyone so that delayed bets never depend on the owner being online.
+    /// Parameters:
+    /// - market_id: The ID of the in-play market.
+    /// - max_bets: The maximum number of delayed bets to release or reject in this call.
+    /// Returns: The number of bets released or rejected.
+    #[endpoint(processDelayedBets)]
+    fn process_delayed_bets(&self, market_id: u64, max_bets: usize) -> usize {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let market = self.markets(market_id).get();
+        let suspended = market.market_status == MarketStatus::Suspended;
+        require!(market.market_status == MarketStatus::Open || suspended, ERR_MARKET_NOT_OPEN);
+        self.require_not_paused(market_id);
+
+        let bet_delay = self.market_bet_delay(market_id).get();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let mut released = 0usize;
+
+        while released < max_bets {
+            let bet_id = match self.delayed_bets(market_id).front() {
+                Some(bet_id) => bet_id,
+                None => break,
+            };
+
+            // Skip bets that were cancelled while waiting
+            if self.bet_by_id(bet_id).is_empty() {
+                self.delayed_bets(market_id).pop_front();
+                continue;
+            }
+
+            let bet = self.bet_by_id(bet_id).get();
+            if bet.status != BetStatus::Pending {
+                self.delayed_bets(market_id).pop_front();
+                continue;
+            }
+            // Bets placed before the suspension must not be matched against the book once it resumes
+            if suspended {
+                self.delayed_bets(market_id).pop_front();
+                self.cancel_unmatched_part(bet);
+                released += 1;
+                continue;
+            }
+            if bet.created_at + bet_delay > current_timestamp {
+                break;
+            }
+
+            self.delayed_bets(market_id).pop_front();
+            self.release_delayed_bet(bet);
+            released += 1;
+
+            // The circuit breaker may have suspended the market
+            if self.markets(market_id).get().market_status != MarketStatus::Open {
+                break;
+            }
+        }
+
+        released
+    }
+
+    /// Sends a pending in-play bet through the matching engine and adjusts the bettor's locked funds.
+    /// Parameters:
+    /// - bet: The pending bet to release.
+    fn release_delayed_bet(&self, mut bet: Bet<Self::Api>) {
+        let bet_id = bet.bet_id;
+        let market_id = bet.event;
+        let selection_id = bet.selection.id;
+        let bettor = bet.bettor.clone();
+        let locked_before = self.calculate_amount_to_lock(&bet, &bet.stake_amount);
+
+        // The bet is indexed again if part of it rests in the book
+        self.user_escrowed_bets(&bettor).swap_remove(&bet_id);
+        bet.status = BetStatus::Unmatched;
+        let (updated_bet, matched_amount, remaining) = self.process_bet(bet);
+        let final_bet = self.update_bet_status(updated_bet, matched_amount.clone(), remaining.clone());
+        self.bet_by_id(bet_id).set(&final_bet);
+        self.update_market_total_matched(market_id, &matched_amount);
+        self.check_circuit_breaker(market_id, selection_id, &final_bet.odd, &matched_amount);
+
+        let locked_after = self.calculate_amount_to_lock(&final_bet, &remaining);
+        if locked_before > locked_after {
+            let unlocked = &locked_before - &locked_after;
+            self.unlock_funds(&bettor, &final_bet.payment_token, &unlocked);
+            self.add_matched_escrow(&final_bet.payment_token, &unlocked);
+        }
     }
 
     //--------------------------------------------------------------------------------------------//
@@ -142,7 +393,7 @@ fn cancel_bet(&self, bet_id: u64) {
     // Verifică dacă bet-ul există
     require!(!self.bet_by_id(bet_id).is_empty(), "Bet does not exist");
     
-    let mut bet = self.bet_by_id(bet_id).get();
+    let bet = self.bet_by_id(bet_id).get();
 
     let (token_identifier, payment_nonce, amount) = self
         .call_value()
@@ -164,119 +415,159 @@ fn cancel_bet(&self, bet_id: u64) {
     );
 
     require!(bet.bettor == caller, "Only the bet owner can cancel the bet");
+    self.require_not_paused(bet.event);
+    require!(
+        self.markets(bet.event).get().market_status != MarketStatus::Suspended,
+        ERR_MARKET_SUSPENDED
+    );
     require!(
-        bet.status == BetStatus::Unmatched || bet.status == BetStatus::PartiallyMatched,
+        bet.status == BetStatus::Unmatched
+            || bet.status == BetStatus::PartiallyMatched
+            || bet.status == BetStatus::Pending,
         "Bet cannot be cancelled in this state"
     );
 
-    let unmatched = &bet.stake_amount - &bet.total_matched;
-    let refund_amount = match bet.bet_type {
-        BetType::Back => {
-            if bet.status == BetStatus::Unmatched {
-                bet.stake_amount.clone()
-            } else {
-                unmatched.clone()
+    if bet.status == BetStatus::PartiallyMatched {
+        // The betslip still represents the matched part
+        self.send().direct_esdt(
+            &caller,
+            &token_identifier_wrap,
+            bet.nft_nonce, 
+            &BigUint::from(1u64)
+        );
+    } else {
+        self.send().esdt_local_burn(
+            &token_identifier_wrap,
+            payment_nonce,
+            &BigUint::from(1u64)
+        );
+    }
+
+    self.cancel_unmatched_part(bet);
+}
+
+    /// Cancels several orders of the caller at once, refunding their unmatched stake to the caller's balance.
+    /// Only orders placed without a betslip NFT (see placeOrders) can be cancelled this way.
+    /// Parameters:
+    /// - bet_ids: The IDs of the orders to cancel.
+    #[endpoint(cancelOrders)]
+    fn cancel_orders(&self, bet_ids: MultiValueEncoded<u64>) {
+        let caller = self.blockchain().get_caller();
+        for bet_id in bet_ids {
+            let bet = self.get_bet(bet_id);
+            self.require_order_cancellable(&caller, &bet);
+            self.require_not_paused(bet.event);
+            self.cancel_unmatched_part(bet);
+        }
+    }
+
+    /// Cancels all open orders of the caller on a market, refunding their unmatched stake to the caller's balance.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    #[endpoint(cancelAllOrders)]
+    fn cancel_all_orders(&self, market_id: u64) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.markets(market_id).get().market_status == MarketStatus::Open,
+            ERR_MARKET_NOT_OPEN
+        );
+        self.require_not_paused(market_id);
+
+        let bet_ids = self.user_open_orders(&caller, market_id).iter().collect::<ManagedVec<u64>>();
+        for bet_id in bet_ids.iter() {
+            if self.bet_by_id(bet_id).is_empty() {
+                continue;
             }
-        },
-        BetType::Lay => {
-            let unmatched_ratio = (&unmatched * &BigUint::from(100u64)) / &bet.stake_amount;
-            let refund = &bet.total_amount * &unmatched_ratio / &BigUint::from(100u64);
-            if bet.status == BetStatus::Unmatched {
-                bet.total_amount.clone()
-            } else {
-                refund
+
+            let bet = self.bet_by_id(bet_id).get();
+            if bet.status == BetStatus::Unmatched
+                || bet.status == BetStatus::PartiallyMatched
+                || bet.status == BetStatus::Pending
+            {
+                self.cancel_unmatched_part(bet);
             }
         }
-    };
 
-    let locked_funds = self.locked_funds(&caller).get();
-    require!(locked_funds >= refund_amount, "Insufficient locked funds to refund");
+        self.user_open_orders(&caller, market_id).clear();
+    }
 
-    let status_for_event: u8 = match &bet.status {
-        BetStatus::Unmatched => {
-            self.selection_unmatched_count(bet.event, bet.selection.id)
-                .update(|val| *val -= 1);
+    /// Cancels the unmatched part of a bet, refunding it to the bettor's balance.
+    /// Fully unmatched bets are deleted, partially matched bets keep only their matched part.
+    /// Parameters:
+    /// - bet: The bet to cancel.
+    /// Returns: The refunded amount.
+    fn cancel_unmatched_part(&self, mut bet: Bet<Self::Api>) -> BigUint {
+        self.fold_legacy_escrow(&bet);
+        let bet_id = bet.bet_id;
+        let bettor = bet.bettor.clone();
+        let unmatched = &bet.stake_amount - &bet.total_matched;
 
-            self.send().esdt_local_burn(
-                &token_identifier_wrap,
-                payment_nonce,
-                &BigUint::from(1u64)
-            );
+        // The whole locked share is released: the refund plus the rounding dust kept by the protocol
+        let released = escrow::locked_share(bet.bet_type, &bet.total_amount, &bet.stake_amount, &unmatched);
+        require!(
+            self.locked_funds(&bettor, &bet.payment_token).get() >= released,
+            ERR_INSUFFICIENT_LOCKED_FUNDS
+        );
+        let (refund_amount, retained) = self.refund_unmatched_escrow(&bet, &unmatched);
 
-            // Utilizează noua funcție de ștergere
-            self.delete_bet(bet_id);
-            
-            BetStatus::Unmatched as u8 
-        },
-        BetStatus::PartiallyMatched => {
-            self.selection_partially_matched_count(bet.event, bet.selection.id)
-                .update(|val| *val -= 1);
-            self.selection_matched_count(bet.event, bet.selection.id)
-                .update(|val| *val += 1);
-
-            bet.status = BetStatus::Matched;
-
-            let original_stake_amount = bet.stake_amount.clone();
-            bet.stake_amount = bet.total_matched.clone();
-            let matched_liability = if bet.bet_type == BetType::Lay {
-                (&bet.liability * &bet.total_matched) / &original_stake_amount
-            } else {
-                BigUint::zero()
-            };
-            if bet.total_matched > BigUint::zero() {
-                bet.total_amount = match bet.bet_type {
-                    BetType::Back => bet.total_matched.clone(),
-                    BetType::Lay => &bet.total_matched + &matched_liability,
-                };
-            } else {
-                bet.total_amount = BigUint::zero();
-            }
-            bet.liability = matched_liability.clone();
-            bet.potential_profit = self.calculate_total_potential_profit(&bet);
+        let status_for_event: u8 = match &bet.status {
+            BetStatus::Unmatched | BetStatus::Pending => {
+                self.record_canceled_bet(bet.event, bet.selection.id, bet.status);
 
-            self.send().direct_esdt(
-                &caller,
-                &token_identifier_wrap,
-                bet.nft_nonce, 
-                &BigUint::from(1u64)
-            );
+                // Utilizează noua funcție de ștergere
+                self.remove_from_orderbook(&bet);
+                self.delete_bet(bet_id);
+                
+                bet.status as u8 
+            },
+            BetStatus::PartiallyMatched => {
+                self.move_status_count(bet.event, bet.selection.id, bet.status, BetStatus::Matched);
+                self.remove_from_orderbook(&bet);
+                self.user_open_orders(&bettor, bet.event).swap_remove(&bet_id);
 
-            self.bet_by_id(bet_id).set(&bet);
-            BetStatus::Matched as u8 
-        },
-        _ => sc_panic!("Invalid bet status for cancellation"),
-    };
+                self.retain_matched_part(&mut bet, retained);
+
+                self.bet_by_id(bet_id).set(&bet);
+                BetStatus::Matched as u8 
+            },
+            _ => sc_panic!("Invalid bet status for cancellation"),
+        };
+
+        self.cancel_bet_event(
+            &bettor,
+            bet_id,
+            status_for_event,
+            &refund_amount,
+            &bet.total_matched,
+            &bet.total_amount,
+            &bet.potential_profit,
+            &bet.liability,
+            sport_index(bet.sport),
+            bet.nft_nonce
+        );
+
+        refund_amount
+    }
+
+    /// Fails unless the caller can cancel the order without its betslip NFT.
+    /// Parameters:
+    /// - caller: The address requesting the cancellation.
+    /// - bet: The order to cancel.
+    fn require_order_cancellable(&self, caller: &ManagedAddress, bet: &Bet<Self::Api>) {
+        require!(bet.bettor == *caller, ERR_NOT_BET_OWNER);
+        require!(bet.nft_nonce == 0, ERR_BET_HAS_NFT);
+        require!(
+            self.markets(bet.event).get().market_status == MarketStatus::Open,
+            ERR_MARKET_NOT_OPEN
+        );
+        require!(
+            bet.status == BetStatus::Unmatched
+                || bet.status == BetStatus::PartiallyMatched
+                || bet.status == BetStatus::Pending,
+            ERR_BET_CANNOT_BE_CANCELLED
+        );
+    }
 
-    self.locked_funds(&caller).update(|val| {
-        if *val >= refund_amount {
-            *val -= &refund_amount;
-        } else {
-            *val = BigUint::zero();
-        }
-    });
-    self.send().direct(&caller, &bet.payment_token, 0, &refund_amount);
-
-    let sport_index = match bet.sport {
-        Sport::Football => 1u8,
-        Sport::Basketball => 2u8,
-        Sport::CounterStrike => 3u8,
-        Sport::Dota => 4u8,
-        Sport::LeagueOfLegends => 5u8,
-    };
-
-    self.cancel_bet_event(
-        &caller,
-        bet_id,
-        status_for_event,
-        &refund_amount,
-        &bet.total_matched,
-        &bet.total_amount,
-        &bet.potential_profit,
-        &bet.liability,
-        sport_index,
-        bet.nft_nonce
-    );
-}
     //--------------------------------------------------------------------------------------------//
     //-------------------------------- Helper Functions ------------------------------------------//
     //--------------------------------------------------------------------------------------------//
@@ -320,7 +611,7 @@ fn cancel_bet(&self, bet_id: u64) {
     /// Returns: A new Bet object.
     fn create_bet(
         &self,
-        sport: Sport,
+        sport: SportId,
         market_id: u64,
         selection_id: u64,
         caller: &ManagedAddress<Self::Api>,
@@ -390,28 +681,15 @@ fn cancel_bet(&self, bet_id: u64) {
         bet
     }
 
-    /// Updates the market and selection data after a bet is placed.
+    /// Adds the amount matched by a bet to the market total. Per-selection totals are kept by the matching engine.
     /// Parameters:
     /// - market_id: The ID of the market.
-    /// - selection_id: The ID of the selection.
     /// - matched_amount: The amount matched in the bet.
-    fn update_market_and_selection(
-        &self,
-        market_id: u64,
-        selection_id: u64,
-        matched_amount: &BigUint,
-    ) {
+    fn update_market_total_matched(&self, market_id: u64, matched_amount: &BigUint) {
+        if *matched_amount == BigUint::zero() {
+            return;
+        }
         let mut market = self.markets(market_id).get();
-        let selection_index = market
-            .selections
-            .iter()
-            .position(|s| s.id == selection_id)
-            .unwrap_or_else(|| sc_panic!(crate::errors::ERR_INVALID_SELECTION));
-        
-        let mut selection = market.selections.get(selection_index);
-        selection.priority_queue = self.selection_tracker(market_id, selection_id).get();
-        
-        let _ = market.selections.set(selection_index, selection);
         market.total_matched_amount += matched_amount;
         self.markets(market_id).set(&market);
     }
@@ -434,14 +712,6 @@ fn cancel_bet(&self, bet_id: u64) {
         nft_nonce: u64, 
         bet_id: u64,
     ) {
-        let sport_index = match bet.sport {
-            Sport::Football => 1u8,
-            Sport::Basketball => 2u8,
-            Sport::CounterStrike => 3u8,
-            Sport::Dota => 4u8,
-            Sport::LeagueOfLegends => 5u8,
-        };
-        
         let user_hex = bet.bettor.hex_expr();
         
         let bet_type_value = match bet.bet_type {
@@ -456,6 +726,8 @@ fn cancel_bet(&self, bet_id: u64) {
             BetStatus::Win => 4u8,
             BetStatus::Lost => 5u8,
             BetStatus::Claimed => 6u8,
+            BetStatus::Pending => 7u8,
+            BetStatus::Void => 8u8,
         };
         
         let potential_profit = match bet.bet_type {
@@ -465,7 +737,7 @@ fn cancel_bet(&self, bet_id: u64) {
         
         self.place_bet_event(
             &user_hex,
-            sport_index,
+            sport_index(bet.sport),
             &bet.event,
             &bet.selection.id,
             &bet.stake_amount,
@@ -481,6 +753,15 @@ fn cancel_bet(&self, bet_id: u64) {
         );
     }
 
+    /// Calculates the part of a bet's payment that stays locked while its remainder is unmatched.
+    /// Parameters:
+    /// - bet: The bet whose payment is locked.
+    /// - remaining: The unmatched part of the stake.
+    /// Returns: The amount to lock as BigUint.
+    fn calculate_amount_to_lock(&self, bet: &Bet<Self::Api>, remaining: &BigUint) -> BigUint {
+        escrow::locked_share(bet.bet_type, &bet.total_amount, &bet.stake_amount, remaining)
+    }
+
     /// Calculates the stake and liability for a bet based on its type.
     /// Parameters:
     /// - bet_type: The type of bet (Back or Lay).
@@ -512,8 +793,62 @@ fn cancel_bet(&self, bet_id: u64) {
         }
     }
 
+    /// Converts a stored bet into the flat DTO returned by the views.
+    /// Parameters:
+    /// - bet: The stored bet.
+    fn build_bet_view(&self, bet: Bet<Self::Api>) -> BetView<Self::Api> {
+        BetView {
+            bet_id: bet.bet_id,
+            bettor: bet.bettor,
+            sport: bet.sport,
+            market_id: bet.event,
+            selection_id: bet.selection.id,
+            selection_type: bet.selection.selection_type,
+            bet_type: bet.bet_type,
+            status: bet.status,
+            odds: bet.odd,
+            stake_amount: bet.stake_amount,
+            liability: bet.liability,
+            total_amount: bet.total_amount,
+            total_matched: bet.total_matched,
+            potential_profit: bet.potential_profit,
+            matched_parts: bet.matched_parts,
+            payment_token: bet.payment_token,
+            payment_nonce: bet.payment_nonce,
+            nft_nonce: bet.nft_nonce,
+            created_at: bet.created_at,
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
     #[view(betExists)]
     fn bet_exists(&self, bet_id: u64) -> bool {
         self.bet_by_id(bet_id).is_empty() == false
     }
+
+    #[view(getBet)]
+    fn get_bet_view(&self, bet_id: u64) -> BetView<Self::Api> {
+        require!(!self.bet_by_id(bet_id).is_empty(), ERR_INVALID_BET_ID);
+        self.build_bet_view(self.bet_by_id(bet_id).get())
+    }
+
+    #[view(getBetByNftNonce)]
+    fn get_bet_by_nft_nonce(&self, nft_nonce: u64) -> BetView<Self::Api> {
+        let bet_id = self.bet_nonce_to_id(nft_nonce).get();
+        require!(bet_id != 0 && !self.bet_by_id(bet_id).is_empty(), ERR_INVALID_BET_ID);
+        self.build_bet_view(self.bet_by_id(bet_id).get())
+    }
+
+    /// Returns the NFT-less orders of a user on a market that still have an unmatched part.
+    #[view(getUserOpenOrders)]
+    fn get_user_open_orders(&self, address: ManagedAddress, market_id: u64) -> MultiValueEncoded<BetView<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for bet_id in self.user_open_orders(&address, market_id).iter() {
+            result.push(self.build_bet_view(self.bet_by_id(bet_id).get()));
+        }
+        result
+    }
 }
\ No newline at end of file
@@ -5,6 +5,30 @@ pub mod constants {
     pub const TOKEN_NAME: &[u8] = b"Betslip";
     pub const TOKEN_TICKER: &[u8] = b"BET";
     pub const DOMAIN_STORAGE: &[u8] = "https://localhost:3001/bet/".as_bytes();
+    pub const ODDS_PRECISION: u64 = 100;
+    pub const MAX_PARLAY_LEGS: usize = 10;
+    pub const VAULT_SHARE_TOKEN_NAME: &[u8] = b"RockstakeLP";
+    pub const VAULT_SHARE_TOKEN_TICKER: &[u8] = b"RSLP";
+    pub const VAULT_SHARE_DECIMALS: usize = 18;
+    pub const BPS_DENOMINATOR: u64 = 10_000;
+    pub const SECONDS_PER_DAY: u64 = 86_400;
+    pub const SECONDS_PER_WEEK: u64 = 604_800;
+    pub const SECONDS_PER_MONTH: u64 = 2_592_000;
+    pub const LIMIT_LOOSENING_DELAY: u64 = 86_400;
+    pub const TOTAL_GOALS_LINE: u64 = 25;
+    pub const NO_LINE: u64 = 0;
+    pub const SPORT_FOOTBALL: u8 = 0;
+    pub const SPORT_BASKETBALL: u8 = 1;
+    pub const SPORT_COUNTER_STRIKE: u8 = 2;
+    pub const SPORT_DOTA: u8 = 3;
+    pub const SPORT_LEAGUE_OF_LEGENDS: u8 = 4;
+    pub const CLOSE_BUCKET_SECONDS: u64 = 3_600;
+    pub const MAX_PAGE_SIZE: usize = 100;
+    pub const MIN_GOVERNANCE_APPROVALS: u64 = 2;
+    pub const MIN_TIMELOCK_DELAY: u64 = 86_400;
+    pub const STORAGE_VERSION: u32 = 1;
+    pub const BET_ENCODING_VERSION: u8 = 1;
+    pub const MARKET_ENCODING_VERSION: u8 = 1;
 
 }
 
@@ -5,6 +5,32 @@ pub const ERR_MARKET_NOT_OPEN: &str = "Market is not open for betting";
 pub const ERR_MARKET_NOT_CLOSED: &str = "Market is not closed";
 pub const ERR_MARKET_ALREADY_EXISTS: &str = "Market already exists";
 pub const ERR_INVALID_SELECTION: &str = "Invalid selection ID";
+pub const ERR_MARKET_NOT_SUSPENDED: &str = "Market is not suspended";
+pub const ERR_MARKET_SUSPENDED: &str = "Market is suspended";
+pub const ERR_MARKET_NOT_EXPIRED: &str = "Market close time has not passed";
+pub const ERR_MARKET_IN_PLAY: &str = "In-play markets are closed by a resolver";
+pub const ERR_SETTLEMENT_ALREADY_STARTED: &str = "Market settlement already started";
+pub const ERR_SETTLEMENT_NOT_STARTED: &str = "Market result not set";
+pub const ERR_NOT_ODDS_ORACLE: &str = "Only a resolver or the odds oracle can set reference odds";
+pub const ERR_ODDS_TOO_FAR_FROM_REFERENCE: &str = "Odds too far from reference price";
+pub const ERR_CANCELLATION_IN_PROGRESS: &str = "Unmatched orders of the market are still being cancelled";
+pub const ERR_NO_CANCELLATION_PENDING: &str = "No cancellation pending for market";
+pub const ERR_MARKET_NOT_MIGRATED: &str = "Market has not been migrated yet";
+
+//Sports
+pub const ERR_SPORT_NOT_FOUND: &str = "Sport is not registered";
+pub const ERR_SPORT_ALREADY_EXISTS: &str = "Sport already registered";
+pub const ERR_SPORT_DISABLED: &str = "Sport is disabled";
+pub const ERR_INVALID_SPORT_ID: &str = "Invalid sport ID";
+pub const ERR_NO_MARKET_TEMPLATES: &str = "Sport needs at least one market template";
+pub const ERR_TEMPLATE_NEEDS_DRAW: &str = "Full time result markets need a result format with draws";
+pub const ERR_INVALID_SCORE: &str = "Score does not match the sport's result format";
+
+//Events
+pub const ERR_EVENT_NOT_FOUND: &str = "Event does not exist";
+pub const ERR_EVENT_ALREADY_EXISTS: &str = "Event already exists";
+pub const ERR_INVALID_EVENT_STATUS: &str = "Invalid event status transition";
+pub const ERR_EVENT_NOT_OPEN: &str = "Event is not scheduled or live";
 
 //Bet
 pub const ERR_ODDS_TOO_LOW: &str = "Odds must be greater than 1.00";
@@ -12,6 +38,9 @@ pub const ERR_INVALID_STAKE: &str = "Invalid stake calculation for Lay bet";
 pub const ERR_INVALID_LIABILITY: &str = "Invalid liability calculation for Lay bet";
 pub const ERR_NOT_BET_OWNER: &str = "Not bet owner";
 pub const ERR_BET_CANNOT_BE_CANCELLED: &str = "Bet cannot be cancelled";
+pub const ERR_DUPLICATE_BET: &str = "Identical bet already placed in this block";
+pub const ERR_BET_HAS_NFT: &str = "Bet must be cancelled with its betslip NFT";
+pub const ERR_NO_ORDERS: &str = "No orders provided";
 pub const ERR_BET_ALREADY_CLAIMED: &str = "Bet already claimed";
 pub const ERR_BET_NOT_WON: &str = "Bet not won";
 
@@ -29,3 +58,69 @@ pub const ERR_INVALID_ROLE: &str = "Unauthorized! Invalid Role";
 pub const ERR_INVALID_PAYMENT_COUNT: &str = "Invalid number of payments";
 pub const ERR_INVALID_BET_ID: &str = "Invalid bet ID";
 
+//Balance
+pub const ERR_NOTHING_TO_WITHDRAW: &str = "Nothing to withdraw";
+pub const ERR_NOTHING_TO_DEPOSIT: &str = "Nothing to deposit";
+pub const ERR_INSUFFICIENT_BALANCE: &str = "Insufficient balance";
+pub const ERR_INVALID_DEPOSIT_TOKEN: &str = "Only fungible tokens can be deposited";
+pub const ERR_INSUFFICIENT_LOCKED_FUNDS: &str = "Insufficient locked funds to refund";
+pub const ERR_NO_ESCROW_DUST: &str = "No escrow dust to sweep";
+
+//Parlay
+pub const ERR_PARLAY_TOO_FEW_LEGS: &str = "A parlay needs at least two legs";
+pub const ERR_PARLAY_TOO_MANY_LEGS: &str = "Too many parlay legs";
+pub const ERR_PARLAY_SAME_EVENT: &str = "Parlay legs must be on different events";
+pub const ERR_PARLAY_LEG_NOT_OFFERED: &str = "Selection not offered for parlays";
+pub const ERR_PARLAY_LEG_IN_PLAY: &str = "Parlay legs must be on markets that have not started";
+pub const ERR_PARLAY_ODDS_BELOW_MIN: &str = "Combined odds below minimum";
+pub const ERR_PARLAY_NOT_FOUND: &str = "Parlay does not exist";
+pub const ERR_PARLAY_NOT_OPEN: &str = "Parlay already settled";
+pub const ERR_PARLAY_LEGS_PENDING: &str = "Parlay legs not yet settled";
+pub const ERR_INSUFFICIENT_HOUSE_LIQUIDITY: &str = "Insufficient house liquidity";
+
+//Vault
+pub const ERR_VAULT_TOKEN_NOT_SET: &str = "Vault token not set";
+pub const ERR_VAULT_TOKEN_LOCKED: &str = "Vault token cannot change while shares exist";
+pub const ERR_INVALID_VAULT_TOKEN: &str = "Invalid vault token";
+pub const ERR_NO_VAULT_STRATEGY: &str = "No vault strategy for market";
+pub const ERR_INVALID_SPREAD: &str = "Invalid spread";
+pub const ERR_NOT_VAULT_KEEPER: &str = "Only a risk manager or the vault keeper can quote";
+pub const ERR_VAULT_SETTLING: &str = "Vault has markets awaiting settlement";
+pub const ERR_VAULT_MARKETS_LIVE: &str = "Vault has markets past their close time or suspended";
+pub const ERR_INSUFFICIENT_VAULT_LIQUIDITY: &str = "Insufficient free vault liquidity";
+pub const ERR_INVALID_SHARES: &str = "Invalid share amount";
+pub const ERR_VAULT_VALUE_LOST: &str = "Vault has shares but no value left";
+
+//Limits
+pub const ERR_SELF_EXCLUDED: &str = "Address is self-excluded";
+pub const ERR_EXCLUSION_CANNOT_BE_SHORTENED: &str = "Self-exclusion can only be extended";
+pub const ERR_MAX_STAKE_EXCEEDED: &str = "Stake exceeds user limit";
+pub const ERR_DEPOSIT_LIMIT_EXCEEDED: &str = "Deposit limit exceeded";
+pub const ERR_LOSS_LIMIT_EXCEEDED: &str = "Loss limit exceeded";
+pub const ERR_NO_PENDING_LIMITS: &str = "No pending limits";
+pub const ERR_PENDING_LIMITS_NOT_DUE: &str = "Pending limits not yet effective";
+
+//Pause
+pub const ERR_NOT_GUARDIAN: &str = "Only a risk manager or the guardian can pause";
+pub const ERR_PAUSED: &str = "Trading is paused";
+pub const ERR_MARKET_PAUSED: &str = "Market is paused";
+pub const ERR_SPORT_PAUSED: &str = "Sport is paused";
+
+//Roles
+pub const ERR_MISSING_ROLE: &str = "Caller does not have the required role";
+pub const ERR_ROLE_ALREADY_GRANTED: &str = "Role already granted";
+pub const ERR_ROLE_NOT_GRANTED: &str = "Role not granted";
+pub const ERR_TIMELOCKED_CHANGE: &str = "Change must go through a governance proposal";
+pub const ERR_ADMINS_BELOW_THRESHOLD: &str = "Not enough admins would remain to reach the required approvals";
+
+//Governance
+pub const ERR_PROPOSAL_NOT_FOUND: &str = "Proposal does not exist";
+pub const ERR_PROPOSAL_NOT_PENDING: &str = "Proposal is not pending";
+pub const ERR_PROPOSAL_ALREADY_APPROVED: &str = "Proposal already approved by caller";
+pub const ERR_PROPOSAL_TIMELOCKED: &str = "Proposal timelock has not elapsed";
+pub const ERR_NOT_ENOUGH_APPROVALS: &str = "Not enough approvals";
+pub const ERR_INVALID_APPROVAL_THRESHOLD: &str = "Invalid approval threshold";
+pub const ERR_TIMELOCK_DELAY_TOO_SHORT: &str = "Timelock delay too short";
+pub const ERR_RESULT_CORRECTION_TOO_LATE: &str = "Market settlement already in progress";
+pub const ERR_RESULT_CORRECTION_PENDING: &str = "A result correction is pending for the market";
+pub const ERR_NOT_PROPOSER: &str = "Only the proposer can cancel a proposal while still an admin";
@@ -0,0 +1,121 @@
+use crate::types::BetType;
+multiversx_sc::imports!();
+
+//--------------------------------------------------------------------------------------------//
+//-------------------------------- Fixed-Point Arithmetic ------------------------------------//
+//--------------------------------------------------------------------------------------------//
+
+/// Computes `value * numerator / denominator`, rounded down. The product is taken before the
+/// division, so no precision is lost to an intermediate ratio.
+/// Parameters:
+/// - value: The amount being scaled.
+/// - numerator: The numerator of the fraction.
+/// - denominator: The denominator of the fraction, must not be zero.
+pub fn mul_div_floor<M: ManagedTypeApi>(
+    value: &BigUint<M>,
+    numerator: &BigUint<M>,
+    denominator: &BigUint<M>,
+) -> BigUint<M> {
+    (value * numerator) / denominator
+}
+
+/// Computes `value * numerator / denominator`, rounded up.
+/// Parameters:
+/// - value: The amount being scaled.
+/// - numerator: The numerator of the fraction.
+/// - denominator: The denominator of the fraction, must not be zero.
+pub fn mul_div_ceil<M: ManagedTypeApi>(
+    value: &BigUint<M>,
+    numerator: &BigUint<M>,
+    denominator: &BigUint<M>,
+) -> BigUint<M> {
+    let product = value * numerator;
+    let quotient = &product / denominator;
+    if &quotient * denominator == product {
+        quotient
+    } else {
+        quotient + BigUint::from(1u64)
+    }
+}
+
+//--------------------------------------------------------------------------------------------//
+//-------------------------------- Escrow Shares ---------------------------------------------//
+//--------------------------------------------------------------------------------------------//
+
+// A bet escrows its whole payment (`total_amount`) against its stake. A back bet escrows exactly its
+// stake; a lay bet escrows stake plus liability, so a part of its stake carries the share
+// `total_amount * part / stake_amount` of the payment, which is generally not a whole amount.
+// The protocol rounds in its own favor: shares it holds back are rounded up, shares it pays out
+// are rounded down, and the difference between the two is dust kept by the protocol.
+
+/// Returns the escrow held for a part of a bet's stake, rounded up. Used for the funds locked
+/// while the part rests unmatched and for the funds released from that lock.
+/// Parameters:
+/// - bet_type: The type of the bet.
+/// - total_amount: The whole payment escrowed by the bet.
+/// - stake_amount: The stake of the bet.
+/// - part: The part of the stake, at most stake_amount.
+pub fn locked_share<M: ManagedTypeApi>(
+    bet_type: BetType,
+    total_amount: &BigUint<M>,
+    stake_amount: &BigUint<M>,
+    part: &BigUint<M>,
+) -> BigUint<M> {
+    match bet_type {
+        BetType::Back => part.clone(),
+        BetType::Lay => {
+            if *stake_amount == BigUint::zero() {
+                return BigUint::zero();
+            }
+            mul_div_ceil(total_amount, part, stake_amount)
+        },
+    }
+}
+
+/// Returns the escrow refunded for a part of a bet's stake, rounded down.
+/// Parameters:
+/// - bet_type: The type of the bet.
+/// - total_amount: The whole payment escrowed by the bet.
+/// - stake_amount: The stake of the bet.
+/// - part: The part of the stake, at most stake_amount.
+pub fn refund_share<M: ManagedTypeApi>(
+    bet_type: BetType,
+    total_amount: &BigUint<M>,
+    stake_amount: &BigUint<M>,
+    part: &BigUint<M>,
+) -> BigUint<M> {
+    match bet_type {
+        BetType::Back => part.clone(),
+        BetType::Lay => {
+            if *stake_amount == BigUint::zero() {
+                return BigUint::zero();
+            }
+            mul_div_floor(total_amount, part, stake_amount)
+        },
+    }
+}
+
+/// Splits off the unmatched part of a bet's stake: returns (refund, retained, dust), where the
+/// refund goes back to the bettor, the retained amount stays escrowed for the matched part and
+/// the dust is the rounding difference kept by the protocol. The three always add up to total_amount.
+/// Parameters:
+/// - bet_type: The type of the bet.
+/// - total_amount: The whole payment escrowed by the bet.
+/// - stake_amount: The stake of the bet.
+/// - unmatched: The unmatched part of the stake, at most stake_amount.
+pub fn split_unmatched<M: ManagedTypeApi>(
+    bet_type: BetType,
+    total_amount: &BigUint<M>,
+    stake_amount: &BigUint<M>,
+    unmatched: &BigUint<M>,
+) -> (BigUint<M>, BigUint<M>, BigUint<M>) {
+    let released = locked_share(bet_type, total_amount, stake_amount, unmatched);
+    let refund = refund_share(bet_type, total_amount, stake_amount, unmatched);
+    let retained = if *total_amount >= released {
+        total_amount - &released
+    } else {
+        BigUint::zero()
+    };
+    let dust = released - &refund;
+    (refund, retained, dust)
+}
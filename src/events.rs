@@ -1,4 +1,4 @@
-use crate::types::{MarketSelectionInfo, MatchedPart};
+use crate::types::{MarketSelectionInfo, MatchedPart, Role};
 
 multiversx_sc::imports!();
 
@@ -50,6 +50,22 @@ pub trait EventsModule {
         #[indexed] nft_nonce: u64,
     );
 
+    #[event("deposit")]
+    fn deposit_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("withdraw")]
+    fn withdraw_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier<Self::Api>,
+        #[indexed] amount: &BigUint,
+    );
+
     #[event("create_market")]
     fn create_market_event(
         &self,
@@ -58,4 +74,258 @@ pub trait EventsModule {
         markets: &ManagedVec<Self::Api, MarketSelectionInfo<Self::Api>>
     );
 
-}
\ No newline at end of file
+    #[event("marketSuspended")]
+    fn market_suspended_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] cancelled_unmatched: bool,
+        #[indexed] timestamp: u64,
+    );
+
+    #[event("marketResumed")]
+    fn market_resumed_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] timestamp: u64,
+    );
+
+    #[event("marketClosed")]
+    fn market_closed_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] timestamp: u64,
+    );
+
+    #[event("marketSettled")]
+    fn market_settled_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] winning_selection: u64,
+        #[indexed] timestamp: u64,
+    );
+
+    #[event("sportRegistered")]
+    fn sport_registered_event(
+        &self,
+        #[indexed] sport_id: u8,
+        #[indexed] name: &ManagedBuffer,
+    );
+
+    #[event("sportEnabledChanged")]
+    fn sport_enabled_changed_event(
+        &self,
+        #[indexed] sport_id: u8,
+        #[indexed] enabled: bool,
+    );
+
+    #[event("sportEventCreated")]
+    fn sport_event_created_event(
+        &self,
+        #[indexed] sport_index: u8,
+        #[indexed] event_id: u64,
+        #[indexed] competition_id: u64,
+        #[indexed] home_participant: &ManagedBuffer,
+        #[indexed] away_participant: &ManagedBuffer,
+        #[indexed] scheduled_start: u64,
+    );
+
+    #[event("sportEventStatusChanged")]
+    fn sport_event_status_changed_event(
+        &self,
+        #[indexed] sport_index: u8,
+        #[indexed] event_id: u64,
+        #[indexed] status: u8,
+        #[indexed] scheduled_start: u64,
+    );
+
+    #[event("marketVoided")]
+    fn market_voided_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] timestamp: u64,
+    );
+
+    #[event("parlayPlaced")]
+    fn parlay_placed_event(
+        &self,
+        #[indexed] bettor: &ManagedAddress,
+        #[indexed] parlay_id: u64,
+        #[indexed] legs_count: usize,
+        #[indexed] stake_amount: &BigUint,
+        #[indexed] combined_odds: &BigUint,
+        #[indexed] potential_payout: &BigUint,
+    );
+
+    #[event("parlaySettled")]
+    fn parlay_settled_event(
+        &self,
+        #[indexed] bettor: &ManagedAddress,
+        #[indexed] parlay_id: u64,
+        #[indexed] status: u8,
+        #[indexed] payout: &BigUint,
+    );
+
+    #[event("houseFunded")]
+    fn house_funded_event(
+        &self,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("houseWithdrawn")]
+    fn house_withdrawn_event(
+        &self,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("escrowDustSwept")]
+    fn escrow_dust_swept_event(
+        &self,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("vaultDeposit")]
+    fn vault_deposit_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        #[indexed] shares: &BigUint,
+    );
+
+    #[event("vaultWithdraw")]
+    fn vault_withdraw_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        #[indexed] shares: &BigUint,
+    );
+
+    #[event("vaultQuoted")]
+    fn vault_quoted_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] selection_id: u64,
+        #[indexed] reference_odds: &BigUint,
+        #[indexed] back_odds: &BigUint,
+        #[indexed] lay_odds: &BigUint,
+    );
+
+    #[event("referenceOddsUpdated")]
+    fn reference_odds_updated_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] selection_id: u64,
+        #[indexed] odds: &BigUint,
+        #[indexed] timestamp: u64,
+    );
+
+    #[event("userLimitsUpdated")]
+    fn user_limits_updated_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] effective_at: u64,
+    );
+
+    #[event("selfExcluded")]
+    fn self_excluded_event(
+        &self,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] until: u64,
+    );
+
+    #[event("globalPauseChanged")]
+    fn global_pause_changed_event(
+        &self,
+        #[indexed] paused: bool,
+        #[indexed] caller: &ManagedAddress,
+    );
+
+    #[event("marketPauseChanged")]
+    fn market_pause_changed_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] paused: bool,
+        #[indexed] caller: &ManagedAddress,
+    );
+
+    #[event("sportPauseChanged")]
+    fn sport_pause_changed_event(
+        &self,
+        #[indexed] sport_index: u8,
+        #[indexed] paused: bool,
+        #[indexed] caller: &ManagedAddress,
+    );
+
+    #[event("circuitBreakerTripped")]
+    fn circuit_breaker_tripped_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] selection_id: u64,
+        #[indexed] matched_volume: &BigUint,
+        #[indexed] odds: &BigUint,
+        #[indexed] timestamp: u64,
+    );
+
+    #[event("roleGranted")]
+    fn role_granted_event(
+        &self,
+        #[indexed] role: Role,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] granted_by: &ManagedAddress,
+    );
+
+    #[event("roleRevoked")]
+    fn role_revoked_event(
+        &self,
+        #[indexed] role: Role,
+        #[indexed] address: &ManagedAddress,
+        #[indexed] revoked_by: &ManagedAddress,
+    );
+
+    #[event("proposalCreated")]
+    fn proposal_created_event(
+        &self,
+        #[indexed] proposal_id: u64,
+        #[indexed] proposer: &ManagedAddress,
+        #[indexed] executable_at: u64,
+    );
+
+    #[event("proposalApproved")]
+    fn proposal_approved_event(
+        &self,
+        #[indexed] proposal_id: u64,
+        #[indexed] approver: &ManagedAddress,
+    );
+
+    #[event("proposalExecuted")]
+    fn proposal_executed_event(
+        &self,
+        #[indexed] proposal_id: u64,
+        #[indexed] executor: &ManagedAddress,
+    );
+
+    #[event("proposalCancelled")]
+    fn proposal_cancelled_event(
+        &self,
+        #[indexed] proposal_id: u64,
+        #[indexed] cancelled_by: &ManagedAddress,
+    );
+
+    #[event("storageMigrated")]
+    fn storage_migrated_event(
+        &self,
+        #[indexed] from_version: u32,
+        #[indexed] to_version: u32,
+    );
+
+    #[event("marketMigrated")]
+    fn market_migrated_event(
+        &self,
+        #[indexed] market_id: u64,
+        #[indexed] storage_version: u32,
+    );
+
+}
@@ -1,4 +1,4 @@
-use crate::{errors::{ERR_BET_ALREADY_CLAIMED, ERR_BET_NOT_WON, ERR_INVALID_MARKET, ERR_MARKET_NOT_CLOSED, ERR_NOT_BET_OWNER, ERR_NO_MARKETS_FOUND}, types::{BetStatus, BetType, MarketStatus, MarketType, Sport, Tracker}};
+use crate::{errors::{ERR_BET_ALREADY_CLAIMED, ERR_BET_NOT_WON, ERR_INVALID_EVENT_STATUS, ERR_INVALID_MARKET, ERR_MARKET_NOT_CLOSED, ERR_NOT_BET_OWNER, ERR_NO_MARKETS_FOUND, ERR_RESULT_CORRECTION_PENDING, ERR_SETTLEMENT_ALREADY_STARTED, ERR_SETTLEMENT_NOT_STARTED}, types::{sport_index, Bet, BetStatus, BetType, MarketStatus, MarketType, Role, SportId, SportEventStatus}};
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
@@ -6,6 +6,12 @@ multiversx_sc::derive_imports!();
 pub trait FundModule:
     crate::storage::StorageModule
     + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::balance::BalanceModule
+    + crate::limits::LimitsModule
+    + crate::pause::PauseModule
+    + crate::sport::SportModule
+    + crate::sport_event::SportEventModule
     + crate::nft::NftModule
     + crate::orderbook::OrderbookModule
     + crate::utils::UtilsModule
@@ -14,52 +20,80 @@ pub trait FundModule:
     //-------------------------------- Market Handling -------------------------------------------//
     //--------------------------------------------------------------------------------------------//
 
-    /// Handles an expired market by closing it and processing unmatched bets.
+    /// Handles an expired market by closing it and scheduling its unmatched bets for refund.
+    /// Refunds are not paid here: they are processed lazily per bet or in resumable batches,
+    /// so closing costs the same gas whatever the size of the market. Each refund also takes
+    /// its bet out of the book, which is empty once the refund cursor reaches zero.
     /// Parameters:
-    /// - sport: The type of sport (e.g., Football, Basketball).
-    /// - event_id: The unique ID of the event.
     /// - market_id: The ID of the market to handle.
-    fn handle_expired_market(&self, sport: Sport, event_id: u64, market_id: u64) {
-        let market_ids = self.markets_by_event_and_sport(sport, event_id).get();
-        require!(
-            market_ids.contains(&market_id),
-            ERR_INVALID_MARKET
-        );
-    
+    fn handle_expired_market(&self, market_id: u64) {
         let mut market = self.markets(market_id).get();
         market.market_status = MarketStatus::Closed;
         self.markets(market_id).set(&market);
+        self.unindex_open_market(market_id);
+        self.delayed_bets(market_id).clear();
+        // The refund cursor covers every bet a pending cancellation has not reached yet
+        self.cancel_cursor(market_id).clear();
         
-        // Colectează toate bet_id-urile din market_bet_ids pentru a le procesa
-        let bet_ids = self.market_bet_ids(market_id).iter().collect::<ManagedVec<u64>>();
+        // Refunds are walked from the last bet down, see return_unmatched_from_cursor
+        self.refund_cursor(market_id).set(self.market_bet_ids(market_id).len());
         
-        // Procesează fiecare bet_id
-        for bet_id in bet_ids.iter() {
-            self.return_unmatched_amount(bet_id);
+        self.market_closed_event(market_id, self.blockchain().get_block_timestamp());
+    }
+
+    /// Refunds the unmatched part of up to `max_bets` bets of a closed market.
+    /// Parameters:
+    /// - market_id: The ID of the closed market.
+    /// - max_bets: The maximum number of bets to process.
+    /// Returns: The number of bets still waiting to be processed.
+    fn process_refund_batch(&self, market_id: u64, max_bets: usize) -> usize {
+        self.return_unmatched_from_cursor(market_id, self.refund_cursor(market_id), max_bets)
+    }
+
+    /// Cancels the unmatched part of up to `max_bets` bets of a market scheduled by suspend_open_market.
+    /// Unmatched and pending bets are refunded and removed, partially matched bets keep only their matched part.
+    /// Parameters:
+    /// - market_id: The ID of the suspended market.
+    /// - max_bets: The maximum number of bets to process.
+    /// Returns: The number of bets still waiting to be processed.
+    fn process_cancel_batch(&self, market_id: u64, max_bets: usize) -> usize {
+        let remaining = self.return_unmatched_from_cursor(market_id, self.cancel_cursor(market_id), max_bets);
+        if remaining == 0 {
+            self.cancel_cursor(market_id).clear();
         }
-        
-        // După procesare, curăță toate datele asociate pentru fiecare selecție
-        for selection in market.selections.iter() {
-            self.selection_back_levels(market_id, selection.id).set(&ManagedVec::new());
-            self.selection_lay_levels(market_id, selection.id).set(&ManagedVec::new());
-            self.selection_back_liquidity(market_id, selection.id).set(&BigUint::zero());
-            self.selection_lay_liquidity(market_id, selection.id).set(&BigUint::zero());
-    
-            let tracker = Tracker {
-                back_levels: ManagedVec::new(),
-                lay_levels: ManagedVec::new(),
-                back_liquidity: BigUint::zero(),
-                lay_liquidity: BigUint::zero(),
-                matched_count: self.selection_matched_count(market_id, selection.id).get(),
-                unmatched_count: self.selection_unmatched_count(market_id, selection.id).get(),
-                partially_matched_count: self.selection_partially_matched_count(market_id, selection.id).get(),
-                win_count: self.selection_win_count(market_id, selection.id).get(),
-                lost_count: self.selection_lost_count(market_id, selection.id).get(),
-                canceled_count: self.selection_canceled_count(market_id, selection.id).get(),
-            };
-            
-            self.selection_tracker(market_id, selection.id).set(&tracker);
+        remaining
+    }
+
+    /// Returns the unmatched part of up to `max_bets` bets of a market, walking a cursor over its bets.
+    /// The cursor walks `market_bet_ids` from the last index down, so bets removed while refunding
+    /// (which swap the last bet into their slot) never cause an unprocessed bet to be skipped.
+    /// Refunding a bet is idempotent, so a bet visited twice is never paid twice.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - cursor_mapper: The storage holding the cursor.
+    /// - max_bets: The maximum number of bets to process.
+    /// Returns: The number of bets still waiting to be processed.
+    fn return_unmatched_from_cursor(
+        &self,
+        market_id: u64,
+        cursor_mapper: SingleValueMapper<usize>,
+        max_bets: usize,
+    ) -> usize {
+        let bet_ids = self.market_bet_ids(market_id);
+        let mut cursor = cursor_mapper.get();
+        let mut processed = 0usize;
+
+        while cursor > 0 && processed < max_bets {
+            if cursor <= bet_ids.len() {
+                let bet_id = bet_ids.get_by_index(cursor);
+                self.return_unmatched_amount(bet_id);
+            }
+            cursor -= 1;
+            processed += 1;
         }
+
+        cursor_mapper.set(cursor);
+        cursor
     }
 
     //--------------------------------------------------------------------------------------------//
@@ -78,36 +112,16 @@ pub trait FundModule:
         let unmatched = &bet.stake_amount - &bet.total_matched;
     
         if unmatched > BigUint::zero() {
-            let original_stake_amount = bet.stake_amount.clone();
-            let refund_amount = match bet.bet_type {
-                BetType::Back => unmatched.clone(),
-                BetType::Lay => {
-                    let unmatched_ratio = (&unmatched * &BigUint::from(100u64)) / &original_stake_amount;
-                    (&bet.total_amount * &unmatched_ratio) / &BigUint::from(100u64)
-                }
-            };
-    
-            self.locked_funds(&bet.bettor).update(|funds| {
-                if *funds >= refund_amount {
-                    *funds -= &refund_amount;
-                } else {
-                    *funds = BigUint::zero();
-                }
-            });
-            self.send().direct(&bet.bettor, &bet.payment_token, bet.payment_nonce, &refund_amount);
-    
+            self.fold_legacy_escrow(&bet);
+            self.remove_from_orderbook(&bet);
+            let (_, retained) = self.refund_unmatched_escrow(&bet, &unmatched);
+
             if bet.total_matched > BigUint::zero() {
-                bet.stake_amount = bet.total_matched.clone();
-                bet.total_amount = if bet.bet_type == BetType::Lay {
-                    let matched_liability = (&bet.liability * &bet.total_matched) / &original_stake_amount;
-                    &bet.total_matched + &matched_liability
-                } else {
-                    bet.total_matched.clone()
-                };
-                bet.potential_profit = self.calculate_total_potential_profit(&bet);
-                bet.status = BetStatus::Matched;
+                self.move_status_count(bet.event, bet.selection.id, bet.status, BetStatus::Matched);
+                self.retain_matched_part(&mut bet, retained);
                 self.bet_by_id(bet_id).set(&bet);
             } else {
+                self.record_canceled_bet(bet.event, bet.selection.id, bet.status);
                 // Dacă nu există sumă matched, șterge complet bet-ul și toate referințele
                 self.delete_bet(bet_id);
             }
@@ -118,28 +132,15 @@ pub trait FundModule:
     /// Parameters:
     /// - sport: The type of sport.
     /// - event_id: The unique ID of the event.
-    fn process_unmatched_bets(&self, sport: Sport, event_id: u64) {
+    fn process_unmatched_bets(&self, sport: SportId, event_id: u64) {
         let market_ids = self.markets_by_event_and_sport(sport, event_id).get();
         for market_id in market_ids.iter() {
             let market = self.markets(market_id).get();
             
             for selection in market.selections.iter() {
-                let back_levels = self.selection_back_levels(market_id, selection.id).get();
-                for level in back_levels.iter() {
-                    for bet_nonce in level.bet_ids.iter() {
-                        self.process_unmatched_bet(bet_nonce);
-                    }
+                for bet_nonce in self.get_resting_bet_ids(market_id, selection.id).iter() {
+                    self.process_unmatched_bet(bet_nonce);
                 }
-
-                let lay_levels = self.selection_lay_levels(market_id, selection.id).get();
-                for level in lay_levels.iter() {
-                    for bet_nonce in level.bet_ids.iter() {
-                        self.process_unmatched_bet(bet_nonce);
-                    }
-                }
-
-                self.selection_back_liquidity(market_id, selection.id).set(&BigUint::zero());
-                self.selection_lay_liquidity(market_id, selection.id).set(&BigUint::zero());
             }
         }
     }
@@ -148,62 +149,42 @@ pub trait FundModule:
     /// Parameters:
     /// - bet_nonce: The unique identifier (nonce) of the bet.
     fn process_unmatched_bet(&self, bet_nonce: u64) {
-        let mut bet = self.bet_by_id(bet_nonce).get();
-        let unmatched = &bet.stake_amount - &bet.total_matched;
-    
-        if unmatched > BigUint::zero() {
-            let refund_amount = match bet.bet_type {
-                BetType::Back => unmatched.clone(),
-                BetType::Lay => {
-                    let unmatched_ratio = (&unmatched * &BigUint::from(100u64)) / &bet.stake_amount;
-                    (&bet.total_amount * &unmatched_ratio) / &BigUint::from(100u64)
-                }
-            };
-    
-            self.send().direct(
-                &bet.bettor,
-                &bet.payment_token,
-                bet.payment_nonce,
-                &refund_amount,
-            );
-    
-            if bet.total_matched > BigUint::zero() {
-                bet.stake_amount = bet.total_matched.clone();
-                bet.total_amount = if bet.bet_type == BetType::Lay {
-                    let matched_ratio = &bet.total_matched / &bet.stake_amount;
-                    &bet.total_amount * &matched_ratio
-                } else {
-                    bet.total_matched.clone()
-                };
-                bet.status = BetStatus::Matched;
-                bet.potential_profit = self.calculate_total_potential_profit(&bet); 
-                self.bet_by_id(bet_nonce).set(&bet);
-            } else {
-                self.bet_by_id(bet_nonce).clear();
-            }
-        }
+        self.return_unmatched_amount(bet_nonce);
     }
 
     //--------------------------------------------------------------------------------------------//
     //-------------------------------- Event Settlement ------------------------------------------//
     //--------------------------------------------------------------------------------------------//
 
-    /// Sets the result of an event and settles the associated markets (only owner).
+    /// Sets the result of an event and starts the settlement of the associated markets (resolver role).
+    /// Bets are not settled here: each market is settled in resumable batches via settleMarketBatch,
+    /// so the event stays settleable however many bets its markets hold.
     /// Parameters:
     /// - sport: The type of sport.
     /// - event_id: The unique ID of the event.
     /// - score_home: The score of the home team.
     /// - score_away: The score of the away team.
-    #[only_owner]
     #[endpoint(setEventScore)]
     fn set_event_score(
         &self,
-        sport: Sport,
+        sport: SportId,
         event_id: u64,
         score_home: u32,
         score_away: u32
     ) {
-        self.event_score(event_id).set(&(score_home, score_away));
+        self.require_role(Role::Resolver);
+        self.validate_score(sport, score_home, score_away);
+
+        if !self.sport_events(sport, event_id).is_empty() {
+            let mut event = self.sport_events(sport, event_id).get();
+            require!(
+                event.status == SportEventStatus::Scheduled || event.status == SportEventStatus::Live,
+                ERR_INVALID_EVENT_STATUS
+            );
+            event.status = SportEventStatus::Finished;
+            self.save_event_status(&event);
+        }
+        self.event_score(sport, event_id).set((score_home, score_away));
         
         let market_ids = self.markets_by_event_and_sport(sport, event_id).get();
         require!(
@@ -212,82 +193,185 @@ pub trait FundModule:
         );
         
         for market_id in market_ids.iter() {
-            let mut market = self.markets(market_id).get();
+            let market = self.markets(market_id).get();
             
             require!(
                 market.market_status == MarketStatus::Closed,
                 ERR_MARKET_NOT_CLOSED
             );
+            require!(
+                self.winning_selection(market_id).is_empty(),
+                ERR_SETTLEMENT_ALREADY_STARTED
+            );
             
-            let winning_selection = self.determine_winner(sport, market.market_type, score_home, score_away, event_id);
+            let winning_selection = self.determine_winner(market_id, score_home, score_away);
             
             self.winning_selection(market_id).set(winning_selection);
-            
+            self.settlement_cursor(market_id).set(self.market_bet_ids(market_id).len());
+        }
+    }
+
+    /// Voids a closed market whose event cannot be resulted (e.g. abandoned), so every bet on it is refunded (resolver role).
+    /// Like setEventScore, this only starts the settlement: refunds are processed through settleMarketBatch.
+    /// Parameters:
+    /// - market_id: The ID of the market to void.
+    #[endpoint(voidMarket)]
+    fn void_market(&self, market_id: u64) {
+        self.require_role(Role::Resolver);
+        self.require_not_timelocked();
+        self.apply_market_void(market_id);
+    }
+
+    /// Settles up to `max_bets` bets of a market whose result is known, callable by anyone.
+    /// The cursor walks `market_bet_ids` from the last index down, and only `Matched` bets are moved
+    /// to `Win` or `Lost`, so no bet is ever settled twice. The market becomes `Settled` once the
+    /// cursor reaches zero. Blocked while a correction of the market's result is pending.
+    /// Parameters:
+    /// - market_id: The ID of the market to settle.
+    /// - max_bets: The maximum number of bets to process in this call.
+    /// Returns: The number of bets still waiting to be settled.
+    #[endpoint(settleMarketBatch)]
+    fn settle_market_batch(&self, market_id: u64, max_bets: usize) -> usize {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        self.require_not_paused(market_id);
+        let mut market = self.markets(market_id).get();
+        require!(
+            market.market_status == MarketStatus::Closed,
+            ERR_MARKET_NOT_CLOSED
+        );
+        let is_void = self.market_void(market_id).get();
+        require!(
+            !self.winning_selection(market_id).is_empty() || is_void,
+            ERR_SETTLEMENT_NOT_STARTED
+        );
+        require!(self.pending_result_corrections(market_id).get() == 0, ERR_RESULT_CORRECTION_PENDING);
+
+        let winning_selection = self.winning_selection(market_id).get();
+        let bet_ids = self.market_bet_ids(market_id);
+        let mut cursor = self.settlement_cursor(market_id).get();
+        let mut processed = 0usize;
+
+        while cursor > 0 && processed < max_bets {
+            if cursor <= bet_ids.len() {
+                let bet_id = bet_ids.get_by_index(cursor);
+                if is_void {
+                    self.void_bet(bet_id);
+                } else {
+                    self.settle_bet(market_id, bet_id, winning_selection);
+                }
+            }
+            cursor -= 1;
+            processed += 1;
+        }
+
+        self.settlement_cursor(market_id).set(cursor);
+
+        if cursor == 0 {
             market.market_status = MarketStatus::Settled;
             self.markets(market_id).set(&market);
-            
-            self.mark_bets_win_loss(sport, market_id, winning_selection);
+            self.settled_markets().push(&market_id);
+            self.market_settled_event(market_id, winning_selection, self.blockchain().get_block_timestamp());
         }
+
+        cursor
     }
 
-    /// Marks bets as won or lost based on the winning selection.
+    /// Marks a bet as won or lost based on the winning selection.
+    /// Any unmatched remainder is refunded first, since refunds are processed lazily.
     /// Parameters:
-    /// - sport: The type of sport.
     /// - market_id: The ID of the market.
+    /// - bet_id: The ID of the bet to settle.
     /// - winning_selection: The ID of the winning selection.
-    fn mark_bets_win_loss(
+    fn settle_bet(
         &self,
-        _sport: Sport,        
         market_id: u64,
+        bet_id: u64,
         winning_selection: u64,
     ) {
-        let bet_ids = self.market_bet_ids(market_id);
+        self.return_unmatched_amount(bet_id);
+
+        if self.bet_by_id(bet_id).is_empty() {
+            return;
+        }
+
+        let mut bet = self.bet_by_id(bet_id).get();
+        if bet.status != BetStatus::Matched {
+            return;
+        }
+
+        let is_winner = match bet.bet_type {
+            BetType::Back => bet.selection.id == winning_selection,
+            BetType::Lay => bet.selection.id != winning_selection
+        };
         
-        for bet_id in bet_ids.iter() {
-            if !self.bet_by_id(bet_id).is_empty() {
-                let mut bet = self.bet_by_id(bet_id).get();
-                
-                if bet.status == BetStatus::Matched {
-                    let is_winner = match bet.bet_type {
-                        BetType::Back => bet.selection.id == winning_selection,
-                        BetType::Lay => bet.selection.id != winning_selection
-                    };
-                    
-                    bet.status = if is_winner {
-                        BetStatus::Win
-                    } else {
-                        BetStatus::Lost
-                    };
-                    
-                    if is_winner {
-                        self.selection_win_count(market_id, bet.selection.id)
-                            .update(|count| *count += 1);
-                    } else {
-                        self.selection_lost_count(market_id, bet.selection.id)
-                            .update(|count| *count += 1);
-    
-                        if bet.bet_type == BetType::Lay {
-                            self.locked_funds(&bet.bettor).update(|funds| {
-                                if *funds >= bet.stake_amount {
-                                    *funds -= &bet.stake_amount;
-                                } else {
-                                    *funds = BigUint::zero();
-                                }
-                            });
-                            self.send().direct(
-                                &bet.bettor,
-                                &bet.payment_token,
-                                bet.payment_nonce,
-                                &bet.stake_amount
-                            );
-
-                        }
-                    }
-                    
-                    self.bet_by_id(bet_id).set(&bet);
-                }
+        bet.status = if is_winner {
+            BetStatus::Win
+        } else {
+            BetStatus::Lost
+        };
+        self.move_status_count(market_id, bet.selection.id, BetStatus::Matched, bet.status);
+        let max_loss = self.bet_max_loss(&bet);
+        self.release_max_loss(&bet.bettor, &bet.payment_token, &max_loss);
+        
+        if is_winner {
+            self.record_bet_result(&bet.bettor, &bet.payment_token, &BigUint::zero(), &bet.potential_profit);
+
+            if bet.nft_nonce == 0 {
+                // Orders placed without a betslip cannot be claimed, so they are paid out directly
+                let payout = self.calculate_payout(&bet);
+                bet.status = BetStatus::Claimed;
+                self.reduce_matched_escrow(&bet.payment_token, &payout);
+                self.credit_balance(&bet.bettor, &bet.payment_token, &payout);
+
+                self.claim_win_event(
+                    &bet.bettor,
+                    bet_id,
+                    BetStatus::Claimed as u8,
+                    &payout,
+                    sport_index(bet.sport),
+                    0u64
+                );
             }
+        } else {
+            self.record_bet_result(&bet.bettor, &bet.payment_token, &max_loss, &BigUint::zero());
+
+            if bet.bet_type == BetType::Lay {
+                self.reduce_matched_escrow(&bet.payment_token, &bet.stake_amount);
+                self.credit_balance(&bet.bettor, &bet.payment_token, &bet.stake_amount);
+            }
+        }
+        
+        self.bet_by_id(bet_id).set(&bet);
+    }
+
+    /// Refunds a bet of a voided market in full and marks it as void.
+    /// Any unmatched remainder is refunded first, since refunds are processed lazily.
+    /// Parameters:
+    /// - bet_id: The ID of the bet to void.
+    fn void_bet(&self, bet_id: u64) {
+        self.return_unmatched_amount(bet_id);
+
+        if self.bet_by_id(bet_id).is_empty() {
+            return;
+        }
+
+        let mut bet = self.bet_by_id(bet_id).get();
+        if bet.status != BetStatus::Matched {
+            return;
         }
+
+        let refund_amount = match bet.bet_type {
+            BetType::Back => bet.stake_amount.clone(),
+            BetType::Lay => bet.total_amount.clone(),
+        };
+
+        bet.status = BetStatus::Void;
+        self.bet_by_id(bet_id).set(&bet);
+        self.move_status_count(bet.event, bet.selection.id, BetStatus::Matched, BetStatus::Void);
+        self.release_max_loss(&bet.bettor, &bet.payment_token, &self.bet_max_loss(&bet));
+
+        self.reduce_matched_escrow(&bet.payment_token, &refund_amount);
+        self.credit_balance(&bet.bettor, &bet.payment_token, &refund_amount);
     }
 
     /// Allows a bettor to claim win from a winning bet.
@@ -307,6 +391,7 @@ pub trait FundModule:
         require!(bet.bettor == caller, "Only the bet owner can claim the win");
         require!(bet.status != BetStatus::Claimed, "Bet already claimed");
         require!(bet.status == BetStatus::Win, "Bet must be in Won state to claim");
+        self.require_not_paused(bet.event);
 
         require!(
             token_identifier_wrap == self.bet_nft_token().get_token_id(),
@@ -321,20 +406,13 @@ pub trait FundModule:
             "Must send exactly 1 NFT"
         );
 
-        let payout = match bet.bet_type {
-            BetType::Back => &bet.stake_amount + &bet.potential_profit,
-            BetType::Lay => &bet.total_amount + &bet.potential_profit
-        };
+        let payout = self.calculate_payout(&bet);
 
         bet.status = BetStatus::Claimed;
         self.bet_by_id(bet_id).set(&bet);
 
-        self.send().direct(
-            &caller,
-            &bet.payment_token,
-            0,
-            &payout
-        );
+        self.reduce_matched_escrow(&bet.payment_token, &payout);
+        self.credit_balance(&caller, &bet.payment_token, &payout);
 
         self.send().direct_esdt(
             &caller,
@@ -343,20 +421,12 @@ pub trait FundModule:
             &BigUint::from(1u64)
         );
 
-        let sport_index = match bet.sport {
-            Sport::Football => 1u8,
-            Sport::Basketball => 2u8,
-            Sport::CounterStrike => 3u8,
-            Sport::Dota => 4u8,
-            Sport::LeagueOfLegends => 5u8,
-        };
-
         self.claim_win_event(
             &caller,
             bet_id,
             BetStatus::Claimed as u8,
             &payout,
-            sport_index,
+            sport_index(bet.sport),
             bet.nft_nonce
         );
     }
@@ -365,40 +435,60 @@ pub trait FundModule:
     //-------------------------------- Helper Functions ------------------------------------------//
     //--------------------------------------------------------------------------------------------//
 
-    /// Determines the winning selection based on the event result.
+    /// Starts refunding every bet of a closed market, directly or through an executed proposal.
     /// Parameters:
-    /// - sport: The type of sport.
-    /// - market_type: The type of market (e.g., FullTimeResult, TotalGoals).
+    /// - market_id: The ID of the market to void.
+    fn apply_market_void(&self, market_id: u64) {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let market = self.markets(market_id).get();
+        require!(
+            market.market_status == MarketStatus::Closed,
+            ERR_MARKET_NOT_CLOSED
+        );
+        require!(
+            self.winning_selection(market_id).is_empty() && !self.market_void(market_id).get(),
+            ERR_SETTLEMENT_ALREADY_STARTED
+        );
+
+        self.market_void(market_id).set(true);
+        self.settlement_cursor(market_id).set(self.market_bet_ids(market_id).len());
+        self.market_voided_event(market_id, self.blockchain().get_block_timestamp());
+    }
+
+    /// Calculates the amount paid out for a winning bet.
+    /// Parameters:
+    /// - bet: The winning bet.
+    /// Returns: The payout as BigUint.
+    fn calculate_payout(&self, bet: &Bet<Self::Api>) -> BigUint {
+        match bet.bet_type {
+            BetType::Back => &bet.stake_amount + &bet.potential_profit,
+            BetType::Lay => &bet.total_amount + &bet.potential_profit
+        }
+    }
+
+    /// Determines the winning selection of a market based on the event result.
+    /// Parameters:
+    /// - market_id: The ID of the market.
     /// - score_home: The score of the home team.
     /// - score_away: The score of the away team.
-    /// - event_id: The unique ID of the event.
     /// Returns: The ID of the winning selection.
     fn determine_winner(
         &self,
-        sport: Sport,
-        market_type: MarketType,
+        market_id: u64,
         score_home: u32,
         score_away: u32,
-        event_id: u64,
     ) -> u64 {
-        let market_ids = self.markets_by_event_and_sport(sport, event_id).get();
-        let market_id = market_ids.iter()
-            .find(|&id| {
-                let market = self.markets(id).get();
-                market.market_type == market_type
-            })
-            .unwrap_or_else(|| sc_panic!(ERR_INVALID_MARKET));
-        
         let market = self.markets(market_id).get();
         
-        let winning_index = match market_type {
+        let winning_index = match market.market_type {
             MarketType::FullTimeResult => {
                 if score_home > score_away { 0 }
                 else if score_home < score_away { 2 }
                 else { 1 }
             },
             MarketType::TotalGoals => {
-                if score_home + score_away > 2 { 0 }
+                // Lines are in tenths of a goal (25 = 2.5)
+                if u64::from(score_home + score_away) * 10 > self.resolve_market_line(market_id) { 0 }
                 else { 1 }
             },
             MarketType::BothTeamsToScore => {
@@ -0,0 +1,327 @@
+use crate::{
+    constants::constants::{MIN_GOVERNANCE_APPROVALS, MIN_TIMELOCK_DELAY},
+    errors::{ERR_INVALID_APPROVAL_THRESHOLD, ERR_INVALID_MARKET, ERR_MARKET_NOT_CLOSED, ERR_NOT_ENOUGH_APPROVALS, ERR_NOT_PROPOSER, ERR_PROPOSAL_ALREADY_APPROVED, ERR_PROPOSAL_NOT_FOUND, ERR_PROPOSAL_NOT_PENDING, ERR_PROPOSAL_TIMELOCKED, ERR_RESULT_CORRECTION_TOO_LATE, ERR_ROLE_ALREADY_GRANTED, ERR_TIMELOCK_DELAY_TOO_SHORT},
+    types::{MarketStatus, Proposal, ProposalAction, ProposalStatus, Role}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait GovernanceModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::balance::BalanceModule
+    + crate::limits::LimitsModule
+    + crate::pause::PauseModule
+    + crate::sport::SportModule
+    + crate::sport_event::SportEventModule
+    + crate::nft::NftModule
+    + crate::orderbook::OrderbookModule
+    + crate::validation::ValidationModule
+    + crate::utils::UtilsModule
+    + crate::bet::BetModule
+    + crate::fund::FundModule
+    + crate::parlay::ParlayModule
+    + crate::vault::VaultModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Governance Setup ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Enables governance (admin role). Once enabled, sensitive parameter changes can only be made
+    /// through proposals that wait for the timelock and collect the required admin approvals.
+    /// Afterwards the configuration itself can only be changed through a proposal.
+    /// Parameters:
+    /// - required_approvals: The number of admin approvals needed to execute a proposal.
+    /// - delay: The number of seconds a proposal must wait before it can be executed.
+    #[endpoint(setGovernanceConfig)]
+    fn set_governance_config(&self, required_approvals: u64, delay: u64) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        self.validate_governance_config(required_approvals, delay);
+
+        self.required_approvals().set(required_approvals);
+        self.timelock_delay().set(delay);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Proposals -------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Queues a sensitive change (admin role). The proposer's approval is recorded immediately.
+    /// A result correction must be proposed before the market's settlement starts, and blocks the
+    /// settlement of the market and of parlays on it until the proposal is executed or cancelled.
+    /// Parameters:
+    /// - action: The change to apply once the proposal is executed.
+    /// Returns: The ID of the new proposal.
+    #[endpoint(propose)]
+    fn propose(&self, action: ProposalAction<Self::Api>) -> u64 {
+        self.require_role(Role::Admin);
+        let caller = self.blockchain().get_caller();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        if let ProposalAction::CorrectMarketResult(market_id, _) = &action {
+            self.require_result_correctable(*market_id);
+            self.pending_result_corrections(*market_id).update(|count| *count += 1);
+        }
+
+        let proposal_id = self.next_proposal_id().get() + 1;
+        self.next_proposal_id().set(proposal_id);
+
+        let executable_at = current_timestamp + self.timelock_delay().get();
+        let proposal = Proposal {
+            proposal_id,
+            proposer: caller.clone(),
+            action,
+            created_at: current_timestamp,
+            executable_at,
+            status: ProposalStatus::Pending,
+        };
+        self.proposals(proposal_id).set(&proposal);
+        self.pending_proposals().insert(proposal_id);
+        self.proposal_approvals(proposal_id).insert(caller.clone());
+
+        self.proposal_created_event(proposal_id, &caller, executable_at);
+        self.proposal_approved_event(proposal_id, &caller);
+
+        proposal_id
+    }
+
+    /// Approves a pending proposal (admin role).
+    /// Parameters:
+    /// - proposal_id: The ID of the proposal.
+    #[endpoint(approveProposal)]
+    fn approve_proposal(&self, proposal_id: u64) {
+        self.require_role(Role::Admin);
+        self.require_pending_proposal(proposal_id);
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.proposal_approvals(proposal_id).insert(caller.clone()),
+            ERR_PROPOSAL_ALREADY_APPROVED
+        );
+
+        self.proposal_approved_event(proposal_id, &caller);
+    }
+
+    /// Cancels a pending proposal (admin role). Only the proposer can cancel it, so that a single
+    /// admin cannot veto the changes the other admins approve, unless the proposer is no longer an admin.
+    /// Parameters:
+    /// - proposal_id: The ID of the proposal.
+    #[endpoint(cancelProposal)]
+    fn cancel_proposal(&self, proposal_id: u64) {
+        self.require_role(Role::Admin);
+        let mut proposal = self.require_pending_proposal(proposal_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            proposal.proposer == caller || !self.has_role_internal(Role::Admin, &proposal.proposer),
+            ERR_NOT_PROPOSER
+        );
+        self.release_result_correction(&proposal.action);
+
+        proposal.status = ProposalStatus::Cancelled;
+        self.proposals(proposal_id).set(&proposal);
+        self.pending_proposals().swap_remove(&proposal_id);
+
+        self.proposal_cancelled_event(proposal_id, &caller);
+    }
+
+    /// Executes a proposal whose timelock has elapsed and which holds enough approvals (admin role).
+    /// Only approvals from addresses that are still admins are counted.
+    /// Parameters:
+    /// - proposal_id: The ID of the proposal.
+    #[endpoint(executeProposal)]
+    fn execute_proposal(&self, proposal_id: u64) {
+        self.require_role(Role::Admin);
+        let mut proposal = self.require_pending_proposal(proposal_id);
+        require!(
+            self.blockchain().get_block_timestamp() >= proposal.executable_at,
+            ERR_PROPOSAL_TIMELOCKED
+        );
+        require!(
+            self.count_valid_approvals(proposal_id) >= self.required_approvals().get(),
+            ERR_NOT_ENOUGH_APPROVALS
+        );
+
+        proposal.status = ProposalStatus::Executed;
+        self.proposals(proposal_id).set(&proposal);
+        self.pending_proposals().swap_remove(&proposal_id);
+        self.release_result_correction(&proposal.action);
+
+        self.apply_proposal_action(proposal.action);
+
+        self.proposal_executed_event(proposal_id, &self.blockchain().get_caller());
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Applies the change carried by an executed proposal.
+    /// Parameters:
+    /// - action: The change to apply.
+    fn apply_proposal_action(&self, action: ProposalAction<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        match action {
+            ProposalAction::SetKeeperReward(reward) => {
+                self.keeper_reward().set(&reward);
+            },
+            ProposalAction::SetCircuitBreaker(config) => {
+                self.circuit_breaker_config().set(&config);
+            },
+            ProposalAction::SetMaxOddsDeviation(market_id, max_deviation_bps) => {
+                require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+                self.max_odds_deviation_bps(market_id).set(max_deviation_bps);
+            },
+            ProposalAction::SetGuardian(guardian) => {
+                self.guardian().set(&guardian);
+            },
+            ProposalAction::SetOddsOracle(oracle) => {
+                self.odds_oracle().set(&oracle);
+            },
+            ProposalAction::GrantRole(role, address) => {
+                require!(self.role_members(role).insert(address.clone()), ERR_ROLE_ALREADY_GRANTED);
+                self.role_granted_event(role, &address, &caller);
+            },
+            ProposalAction::RevokeRole(role, address) => {
+                self.remove_role_member(role, &address);
+                self.role_revoked_event(role, &address, &caller);
+            },
+            ProposalAction::CorrectMarketResult(market_id, winning_selection) => {
+                self.correct_market_result(market_id, winning_selection);
+            },
+            ProposalAction::SetGovernanceConfig(required_approvals, delay) => {
+                self.validate_governance_config(required_approvals, delay);
+                self.required_approvals().set(required_approvals);
+                self.timelock_delay().set(delay);
+            },
+            ProposalAction::WithdrawHouse(token, amount, recipient) => {
+                self.apply_house_withdrawal(&token, &amount, &recipient);
+            },
+            ProposalAction::SetVaultToken(token) => {
+                self.apply_vault_token(&token);
+            },
+            ProposalAction::SetVaultKeeper(keeper) => {
+                self.vault_keeper().set(&keeper);
+            },
+            ProposalAction::SetVaultStrategy(market_id, strategy) => {
+                self.apply_vault_strategy(market_id, &strategy);
+            },
+            ProposalAction::SetParlayLegOdds(market_id, selection_id, odds) => {
+                self.apply_parlay_leg_odds(market_id, selection_id, &odds);
+            },
+            ProposalAction::SetSportTemplates(sport_id, market_templates) => {
+                self.apply_sport_templates(sport_id, market_templates);
+            },
+            ProposalAction::SetSportEnabled(sport_id, enabled) => {
+                self.apply_sport_enabled(sport_id, enabled);
+            },
+            ProposalAction::VoidMarket(market_id) => {
+                self.apply_market_void(market_id);
+            },
+        }
+    }
+
+    /// Replaces the winning selection of a closed market before any of its bets has been settled.
+    /// Settlement of the market and of parlays on it is blocked while the correction is pending.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - winning_selection: The corrected winning selection.
+    fn correct_market_result(&self, market_id: u64, winning_selection: u64) {
+        self.require_result_correctable(market_id);
+        self.validate_selection(market_id, winning_selection);
+
+        self.winning_selection(market_id).set(winning_selection);
+    }
+
+    /// Fails unless the market is closed with a result whose settlement has not started yet.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn require_result_correctable(&self, market_id: u64) {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let market = self.markets(market_id).get();
+        require!(market.market_status == MarketStatus::Closed, ERR_MARKET_NOT_CLOSED);
+        require!(
+            !self.winning_selection(market_id).is_empty()
+                && self.settlement_cursor(market_id).get() == self.market_bet_ids(market_id).len(),
+            ERR_RESULT_CORRECTION_TOO_LATE
+        );
+    }
+
+    /// Lifts the settlement block of a result correction once its proposal is executed or cancelled.
+    /// Parameters:
+    /// - action: The action of the proposal leaving the pending set.
+    fn release_result_correction(&self, action: &ProposalAction<Self::Api>) {
+        if let ProposalAction::CorrectMarketResult(market_id, _) = action {
+            self.pending_result_corrections(*market_id).update(|count| *count -= 1);
+        }
+    }
+
+    /// Loads a proposal and fails unless it is still pending.
+    /// Parameters:
+    /// - proposal_id: The ID of the proposal.
+    fn require_pending_proposal(&self, proposal_id: u64) -> Proposal<Self::Api> {
+        require!(!self.proposals(proposal_id).is_empty(), ERR_PROPOSAL_NOT_FOUND);
+        let proposal = self.proposals(proposal_id).get();
+        require!(proposal.status == ProposalStatus::Pending, ERR_PROPOSAL_NOT_PENDING);
+        proposal
+    }
+
+    /// Counts the approvals of a proposal given by addresses that still hold the admin role.
+    /// Parameters:
+    /// - proposal_id: The ID of the proposal.
+    fn count_valid_approvals(&self, proposal_id: u64) -> u64 {
+        self.proposal_approvals(proposal_id)
+            .iter()
+            .filter(|approver| self.has_role_internal(Role::Admin, approver))
+            .count() as u64
+    }
+
+    /// Fails unless governance is disabled or needs several approvals the current admins can reach,
+    /// behind a timelock long enough for the other admins to react.
+    /// Parameters:
+    /// - required_approvals: The number of approvals needed, zero disabling governance.
+    /// - delay: The number of seconds a proposal must wait before it can be executed.
+    fn validate_governance_config(&self, required_approvals: u64, delay: u64) {
+        if required_approvals == 0 {
+            return;
+        }
+
+        let admin_count = self.role_members(Role::Admin).len() as u64;
+        require!(
+            required_approvals >= MIN_GOVERNANCE_APPROVALS && required_approvals <= admin_count,
+            ERR_INVALID_APPROVAL_THRESHOLD
+        );
+        require!(delay >= MIN_TIMELOCK_DELAY, ERR_TIMELOCK_DELAY_TOO_SHORT);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(getPendingProposals)]
+    fn get_pending_proposals(&self) -> MultiValueEncoded<Proposal<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for proposal_id in self.pending_proposals().iter() {
+            result.push(self.proposals(proposal_id).get());
+        }
+        result
+    }
+
+    #[view(getProposal)]
+    fn get_proposal(&self, proposal_id: u64) -> Proposal<Self::Api> {
+        require!(!self.proposals(proposal_id).is_empty(), ERR_PROPOSAL_NOT_FOUND);
+        self.proposals(proposal_id).get()
+    }
+
+    #[view(getProposalApprovals)]
+    fn get_proposal_approvals(&self, proposal_id: u64) -> MultiValueEncoded<ManagedAddress> {
+        self.proposal_approvals(proposal_id).iter().collect()
+    }
+
+    /// Returns (required_approvals, timelock_delay); zero required approvals means governance is disabled.
+    #[view(getGovernanceConfig)]
+    fn get_governance_config(&self) -> MultiValue2<u64, u64> {
+        (self.required_approvals().get(), self.timelock_delay().get()).into()
+    }
+}
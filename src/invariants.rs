@@ -0,0 +1,221 @@
+use crate::{
+    errors::ERR_INVALID_MARKET,
+    types::{BalanceViolation, Bet, BetStatus, BetType, InvariantKind, SelectionViolation}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait InvariantsModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::balance::BalanceModule
+    + crate::limits::LimitsModule
+    + crate::nft::NftModule
+    + crate::orderbook::OrderbookModule
+    + crate::validation::ValidationModule
+    + crate::utils::UtilsModule
+    + crate::pause::PauseModule
+    + crate::bet::BetModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Invariant Checks ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Checks the book of a selection: each side's liquidity must equal the sum of its level stakes,
+    /// each level stake must equal the unmatched remainder of the bets queued at that price, and every
+    /// queued bet must be a resting bet of that side and price (reported as QueuedBet, with zero amounts).
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// Returns: One entry per violation, none if the book is consistent.
+    #[view(checkSelectionInvariants)]
+    fn check_selection_invariants(
+        &self,
+        market_id: u64,
+        selection_id: u64,
+    ) -> MultiValueEncoded<SelectionViolation<Self::Api>> {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        self.validate_selection(market_id, selection_id);
+
+        let mut violations = MultiValueEncoded::new();
+        for bet_type in [BetType::Back, BetType::Lay] {
+            let mut levels_total = BigUint::zero();
+            for odds in self.price_levels(market_id, selection_id, bet_type).iter() {
+                let level_stake = self.level_stake(market_id, selection_id, bet_type, &odds).get();
+                levels_total += &level_stake;
+
+                let mut queued_total = BigUint::zero();
+                for node in self.order_queue(market_id, selection_id, bet_type, &odds).iter() {
+                    let bet_id = node.into_value();
+                    if !self.is_resting_at(bet_id, market_id, selection_id, bet_type, &odds) {
+                        violations.push(SelectionViolation {
+                            kind: InvariantKind::QueuedBet,
+                            bet_type,
+                            odds: odds.clone(),
+                            bet_id,
+                            expected: BigUint::zero(),
+                            actual: BigUint::zero(),
+                        });
+                        continue;
+                    }
+                    let bet = self.bet_by_id(bet_id).get();
+                    queued_total += &bet.stake_amount - &bet.total_matched;
+                }
+
+                if queued_total != level_stake {
+                    violations.push(SelectionViolation {
+                        kind: InvariantKind::LevelStake,
+                        bet_type,
+                        odds,
+                        bet_id: 0,
+                        expected: queued_total,
+                        actual: level_stake,
+                    });
+                }
+            }
+
+            let liquidity = self.side_liquidity(market_id, selection_id, bet_type).get();
+            if liquidity != levels_total {
+                violations.push(SelectionViolation {
+                    kind: InvariantKind::SideLiquidity,
+                    bet_type,
+                    odds: BigUint::zero(),
+                    bet_id: 0,
+                    expected: levels_total,
+                    actual: liquidity,
+                });
+            }
+        }
+        violations
+    }
+
+    /// Checks that the locked funds of an address equal the escrow of its bets that are still resting
+    /// in the book or waiting behind the in-play delay, for every token it holds such bets or a free balance in.
+    /// Parameters:
+    /// - address: The address to check.
+    /// Returns: One entry per violation, none if the escrow is consistent.
+    #[view(checkUserInvariants)]
+    fn check_user_invariants(&self, address: ManagedAddress) -> MultiValueEncoded<BalanceViolation<Self::Api>> {
+        let mut tokens: ManagedVec<EgldOrEsdtTokenIdentifier> = ManagedVec::new();
+        let mut escrows: ManagedVec<BigUint> = ManagedVec::new();
+
+        for token in self.claimable_tokens(&address).iter() {
+            tokens.push(token);
+            escrows.push(BigUint::zero());
+        }
+
+        for bet_id in self.user_escrowed_bets(&address).iter() {
+            if self.bet_by_id(bet_id).is_empty() {
+                continue;
+            }
+            let bet = self.bet_by_id(bet_id).get();
+            let escrow = self.outstanding_escrow(&bet);
+
+            match tokens.iter().position(|token| token == bet.payment_token) {
+                Some(index) => {
+                    let total = escrows.get(index).clone_value() + escrow;
+                    let _ = escrows.set(index, total);
+                },
+                None => {
+                    tokens.push(bet.payment_token.clone());
+                    escrows.push(escrow);
+                }
+            }
+        }
+
+        let mut violations = MultiValueEncoded::new();
+        for (index, token) in tokens.iter().enumerate() {
+            let expected = escrows.get(index).clone_value();
+            let actual = self.locked_funds(&address, &token).get();
+            if actual != expected {
+                violations.push(BalanceViolation {
+                    kind: InvariantKind::LockedFunds,
+                    token,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        violations
+    }
+
+    /// Checks that the contract holds enough of a token to cover what it owes: all free balances,
+    /// all locked funds, the escrow of matched bets not paid out yet, the rounding dust, the house pool
+    /// and its reserved payouts, and for EGLD the keeper reward pool.
+    /// The totals only cover balances moved since they were introduced, so this is a lower bound of
+    /// the obligations for contracts holding older bets.
+    /// Parameters:
+    /// - token: The token to check.
+    /// Returns: One entry if the contract balance falls short, none otherwise.
+    #[view(checkContractInvariants)]
+    fn check_contract_invariants(&self, token: EgldOrEsdtTokenIdentifier) -> MultiValueEncoded<BalanceViolation<Self::Api>> {
+        let mut obligations = self.total_claimable_balance(&token).get()
+            + self.total_locked_funds(&token).get()
+            + self.total_matched_escrow(&token).get()
+            + self.escrow_dust(&token).get()
+            + self.house_pool(&token).get()
+            + self.house_reserved(&token).get();
+        if token.is_egld() {
+            obligations += self.keeper_reward_pool().get();
+        }
+
+        let balance = self.blockchain().get_sc_balance(&token, 0);
+
+        let mut violations = MultiValueEncoded::new();
+        if balance < obligations {
+            violations.push(BalanceViolation {
+                kind: InvariantKind::ContractBalance,
+                token,
+                expected: obligations,
+                actual: balance,
+            });
+        }
+        violations
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Returns whether a queued bet still rests on the given side and price of a selection.
+    /// Parameters:
+    /// - bet_id: The ID of the queued bet.
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - bet_type: The side of the book.
+    /// - odds: The price level.
+    fn is_resting_at(
+        &self,
+        bet_id: u64,
+        market_id: u64,
+        selection_id: u64,
+        bet_type: BetType,
+        odds: &BigUint,
+    ) -> bool {
+        if self.bet_by_id(bet_id).is_empty() {
+            return false;
+        }
+        let bet = self.bet_by_id(bet_id).get();
+        bet.event == market_id
+            && bet.selection.id == selection_id
+            && bet.bet_type == bet_type
+            && bet.odd == *odds
+            && (bet.status == BetStatus::Unmatched || bet.status == BetStatus::PartiallyMatched)
+            && bet.total_matched < bet.stake_amount
+    }
+
+    /// Returns the funds a resting or pending bet should hold locked for its unmatched remainder.
+    /// Parameters:
+    /// - bet: The bet.
+    fn outstanding_escrow(&self, bet: &Bet<Self::Api>) -> BigUint {
+        match bet.status {
+            BetStatus::Unmatched | BetStatus::PartiallyMatched | BetStatus::Pending => {
+                let unmatched = &bet.stake_amount - &bet.total_matched;
+                self.calculate_amount_to_lock(bet, &unmatched)
+            },
+            _ => BigUint::zero(),
+        }
+    }
+}
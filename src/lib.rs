@@ -1,35 +1,66 @@
 #![no_std]
 
 pub mod storage;
+pub mod balance;
 pub mod constants;
 pub mod events;
 pub mod bet;
 pub mod errors;
+pub mod escrow;
 pub mod nft;
 pub mod fund;
+pub mod governance;
+pub mod invariants;
+pub mod limits;
 pub mod market;
+pub mod migration;
 pub mod orderbook;
+pub mod parlay;
+pub mod roles;
+pub mod pause;
+pub mod sport;
+pub mod sport_event;
 pub mod validation;
 pub mod types;
 pub mod utils;
+pub mod vault;
 
+use constants::constants::STORAGE_VERSION;
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 #[multiversx_sc::contract]
 pub trait Rockstake:
 storage::StorageModule
 + events::EventsModule
++ roles::RolesModule
++ balance::BalanceModule
++ limits::LimitsModule
 + nft::NftModule
 + fund::FundModule
++ governance::GovernanceModule
++ invariants::InvariantsModule
 + bet::BetModule
 + market::MarketModule
++ migration::MigrationModule
 + orderbook::OrderbookModule
++ parlay::ParlayModule
++ pause::PauseModule
++ sport::SportModule
++ sport_event::SportEventModule
 + validation::ValidationModule
-+ utils::UtilsModule{
++ utils::UtilsModule
++ vault::VaultModule{
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self) {
+        self.migrate_storage();
+        self.seed_initial_admin();
+        self.register_default_sports();
+    }
 
     #[init]
     fn init(&self) {
+        self.storage_version().set(STORAGE_VERSION);
+        self.seed_initial_admin();
+        self.register_default_sports();
     }
 }
\ No newline at end of file
@@ -0,0 +1,364 @@
+use crate::{
+    constants::constants::{LIMIT_LOOSENING_DELAY, SECONDS_PER_DAY, SECONDS_PER_MONTH, SECONDS_PER_WEEK},
+    errors::{ERR_DEPOSIT_LIMIT_EXCEEDED, ERR_EXCLUSION_CANNOT_BE_SHORTENED, ERR_LOSS_LIMIT_EXCEEDED, ERR_MAX_STAKE_EXCEEDED, ERR_NO_PENDING_LIMITS, ERR_PENDING_LIMITS_NOT_DUE, ERR_SELF_EXCLUDED},
+    types::{Bet, BetType, LimitPeriod, PendingUserLimits, PeriodUsage, UserLimits}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait LimitsModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Self-Imposed Limits ---------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Sets the caller's responsible-gambling limits for a token. A zero value means no limit.
+    /// Tightened limits apply immediately; any loosened limit only applies after LIMIT_LOOSENING_DELAY,
+    /// until then the tighter of the current and requested value of each limit is enforced.
+    /// Parameters:
+    /// - token: The token the limits apply to.
+    /// - limits: The requested limits.
+    #[endpoint(setUserLimits)]
+    fn set_user_limits(&self, token: EgldOrEsdtTokenIdentifier, limits: UserLimits<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        let current = self.get_effective_limits(&caller, &token);
+        let tightened = UserLimits {
+            daily_deposit_limit: self.tighter_limit(&current.daily_deposit_limit, &limits.daily_deposit_limit),
+            weekly_deposit_limit: self.tighter_limit(&current.weekly_deposit_limit, &limits.weekly_deposit_limit),
+            monthly_deposit_limit: self.tighter_limit(&current.monthly_deposit_limit, &limits.monthly_deposit_limit),
+            daily_loss_limit: self.tighter_limit(&current.daily_loss_limit, &limits.daily_loss_limit),
+            weekly_loss_limit: self.tighter_limit(&current.weekly_loss_limit, &limits.weekly_loss_limit),
+            monthly_loss_limit: self.tighter_limit(&current.monthly_loss_limit, &limits.monthly_loss_limit),
+            max_stake: self.tighter_limit(&current.max_stake, &limits.max_stake),
+        };
+        self.user_limits(&caller, &token).set(&tightened);
+
+        let loosens = self.is_loosened(&current.daily_deposit_limit, &limits.daily_deposit_limit)
+            || self.is_loosened(&current.weekly_deposit_limit, &limits.weekly_deposit_limit)
+            || self.is_loosened(&current.monthly_deposit_limit, &limits.monthly_deposit_limit)
+            || self.is_loosened(&current.daily_loss_limit, &limits.daily_loss_limit)
+            || self.is_loosened(&current.weekly_loss_limit, &limits.weekly_loss_limit)
+            || self.is_loosened(&current.monthly_loss_limit, &limits.monthly_loss_limit)
+            || self.is_loosened(&current.max_stake, &limits.max_stake);
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        if loosens {
+            let effective_at = current_timestamp + LIMIT_LOOSENING_DELAY;
+            self.pending_user_limits(&caller, &token).set(&PendingUserLimits {
+                limits,
+                effective_at,
+            });
+            self.user_limits_updated_event(&caller, &token, effective_at);
+        } else {
+            self.pending_user_limits(&caller, &token).clear();
+            self.user_limits_updated_event(&caller, &token, current_timestamp);
+        }
+    }
+
+    /// Applies the caller's pending looser limits once their delay has elapsed.
+    /// Pending limits are also applied automatically the next time the limits are enforced.
+    /// Parameters:
+    /// - token: The token the limits apply to.
+    #[endpoint(applyPendingLimits)]
+    fn apply_pending_limits(&self, token: EgldOrEsdtTokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.pending_user_limits(&caller, &token).is_empty(), ERR_NO_PENDING_LIMITS);
+        let pending = self.pending_user_limits(&caller, &token).get();
+        require!(
+            self.blockchain().get_block_timestamp() >= pending.effective_at,
+            ERR_PENDING_LIMITS_NOT_DUE
+        );
+
+        self.get_effective_limits(&caller, &token);
+    }
+
+    /// Drops the caller's pending looser limits, keeping the limits currently enforced.
+    /// Parameters:
+    /// - token: The token the limits apply to.
+    #[endpoint(cancelPendingLimits)]
+    fn cancel_pending_limits(&self, token: EgldOrEsdtTokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.pending_user_limits(&caller, &token).is_empty(), ERR_NO_PENDING_LIMITS);
+        self.pending_user_limits(&caller, &token).clear();
+    }
+
+    /// Excludes the caller from betting and depositing until a timestamp (cooling-off or self-exclusion).
+    /// The exclusion can be extended but never shortened.
+    /// Parameters:
+    /// - until: The timestamp until which the caller is excluded.
+    #[endpoint(selfExclude)]
+    fn self_exclude(&self, until: u64) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            until > self.self_excluded_until(&caller).get()
+                && until > self.blockchain().get_block_timestamp(),
+            ERR_EXCLUSION_CANNOT_BE_SHORTENED
+        );
+
+        self.self_excluded_until(&caller).set(until);
+        self.self_excluded_event(&caller, until);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Enforcement -----------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Fails if the address is self-excluded.
+    /// Parameters:
+    /// - address: The address to check.
+    fn require_not_excluded(&self, address: &ManagedAddress) {
+        require!(
+            self.blockchain().get_block_timestamp() >= self.self_excluded_until(address).get(),
+            ERR_SELF_EXCLUDED
+        );
+    }
+
+    /// Checks a deposit against the address' deposit limits and records it.
+    /// Parameters:
+    /// - address: The depositing address.
+    /// - token: The deposited token.
+    /// - amount: The deposited amount.
+    fn enforce_deposit_limits(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        self.require_not_excluded(address);
+        let limits = self.get_effective_limits(address, token);
+
+        for period in [LimitPeriod::Daily, LimitPeriod::Weekly, LimitPeriod::Monthly] {
+            let limit = match period {
+                LimitPeriod::Daily => &limits.daily_deposit_limit,
+                LimitPeriod::Weekly => &limits.weekly_deposit_limit,
+                LimitPeriod::Monthly => &limits.monthly_deposit_limit,
+            };
+
+            let mapper = self.user_period_usage(address, token, period, self.get_period_index(period));
+            let mut usage = self.get_usage_or_default(&mapper);
+            usage.deposited += amount;
+            require!(
+                *limit == BigUint::zero() || usage.deposited <= *limit,
+                ERR_DEPOSIT_LIMIT_EXCEEDED
+            );
+            mapper.set(&usage);
+        }
+    }
+
+    /// Checks a new bet against the address' exclusion, max stake and loss limits, and reserves
+    /// the most the bet can lose until it is settled or refunded.
+    /// Loss limits count the net losses settled in the period, plus the most the address' open and
+    /// unsettled bets can still lose, plus the most the new bet can lose. Open bets may settle in a
+    /// later period, so their outstanding loss counts against the limits of every period.
+    /// Parameters:
+    /// - address: The bettor.
+    /// - token: The token of the bet.
+    /// - total_amount: The total amount funding the bet.
+    /// - max_loss: The most the bet can lose (the stake of a back bet, the liability of a lay bet).
+    fn enforce_bet_limits(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        total_amount: &BigUint,
+        max_loss: &BigUint,
+    ) {
+        self.require_not_excluded(address);
+        let limits = self.get_effective_limits(address, token);
+        require!(
+            limits.max_stake == BigUint::zero() || *total_amount <= limits.max_stake,
+            ERR_MAX_STAKE_EXCEEDED
+        );
+        let outstanding = self.outstanding_max_loss(address, token).get() + max_loss;
+
+        for period in [LimitPeriod::Daily, LimitPeriod::Weekly, LimitPeriod::Monthly] {
+            let limit = match period {
+                LimitPeriod::Daily => &limits.daily_loss_limit,
+                LimitPeriod::Weekly => &limits.weekly_loss_limit,
+                LimitPeriod::Monthly => &limits.monthly_loss_limit,
+            };
+            if *limit == BigUint::zero() {
+                continue;
+            }
+
+            let mapper = self.user_period_usage(address, token, period, self.get_period_index(period));
+            let usage = self.get_usage_or_default(&mapper);
+            let net_loss = if usage.lost > usage.won {
+                &usage.lost - &usage.won
+            } else {
+                BigUint::zero()
+            };
+            require!(net_loss + &outstanding <= *limit, ERR_LOSS_LIMIT_EXCEEDED);
+        }
+
+        self.outstanding_max_loss(address, token).set(&outstanding);
+    }
+
+    /// Releases part of the loss an address has outstanding in open and unsettled bets.
+    /// Bets placed before the loss was reserved release nothing, so this saturates at zero.
+    /// Parameters:
+    /// - address: The bettor.
+    /// - token: The token of the bet.
+    /// - amount: The loss that can no longer occur.
+    fn release_max_loss(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint) {
+        self.outstanding_max_loss(address, token).update(|outstanding| {
+            if *outstanding >= *amount {
+                *outstanding -= amount;
+            } else {
+                *outstanding = BigUint::zero();
+            }
+        });
+    }
+
+    /// Returns the most a bet can still lose: the stake of a back bet, and for a lay bet the part of
+    /// its escrow above the stake, which unlike `liability` stays in step with the stake while it rests.
+    /// Parameters:
+    /// - bet: The bet.
+    fn bet_max_loss(&self, bet: &Bet<Self::Api>) -> BigUint {
+        match bet.bet_type {
+            BetType::Back => bet.stake_amount.clone(),
+            BetType::Lay => {
+                if bet.total_amount > bet.stake_amount {
+                    &bet.total_amount - &bet.stake_amount
+                } else {
+                    BigUint::zero()
+                }
+            },
+        }
+    }
+
+    /// Records the outcome of a settled bet in the address' loss tracking.
+    /// Parameters:
+    /// - address: The bettor.
+    /// - token: The token of the bet.
+    /// - lost: The amount lost, if the bet lost.
+    /// - won: The profit, if the bet won.
+    fn record_bet_result(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier, lost: &BigUint, won: &BigUint) {
+        for period in [LimitPeriod::Daily, LimitPeriod::Weekly, LimitPeriod::Monthly] {
+            let mapper = self.user_period_usage(address, token, period, self.get_period_index(period));
+            let mut usage = self.get_usage_or_default(&mapper);
+            usage.lost += lost;
+            usage.won += won;
+            mapper.set(&usage);
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Returns the limits in force for an address, promoting pending limits whose delay has elapsed.
+    /// Parameters:
+    /// - address: The address.
+    /// - token: The token the limits apply to.
+    fn get_effective_limits(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier) -> UserLimits<Self::Api> {
+        if !self.pending_user_limits(address, token).is_empty() {
+            let pending = self.pending_user_limits(address, token).get();
+            if self.blockchain().get_block_timestamp() >= pending.effective_at {
+                self.user_limits(address, token).set(&pending.limits);
+                self.pending_user_limits(address, token).clear();
+                return pending.limits;
+            }
+        }
+
+        if self.user_limits(address, token).is_empty() {
+            return self.empty_limits();
+        }
+        self.user_limits(address, token).get()
+    }
+
+    /// Returns the stricter of two limits, where zero means no limit.
+    fn tighter_limit(&self, current: &BigUint, requested: &BigUint) -> BigUint {
+        if *current == BigUint::zero() {
+            return requested.clone();
+        }
+        if *requested == BigUint::zero() || requested > current {
+            return current.clone();
+        }
+        requested.clone()
+    }
+
+    /// Returns true if the requested limit is looser than the current one, where zero means no limit.
+    fn is_loosened(&self, current: &BigUint, requested: &BigUint) -> bool {
+        *current != BigUint::zero() && (*requested == BigUint::zero() || requested > current)
+    }
+
+    /// Returns the index of the current period, counted in fixed windows since the Unix epoch.
+    fn get_period_index(&self, period: LimitPeriod) -> u64 {
+        let period_length = match period {
+            LimitPeriod::Daily => SECONDS_PER_DAY,
+            LimitPeriod::Weekly => SECONDS_PER_WEEK,
+            LimitPeriod::Monthly => SECONDS_PER_MONTH,
+        };
+        self.blockchain().get_block_timestamp() / period_length
+    }
+
+    fn get_usage_or_default(&self, mapper: &SingleValueMapper<PeriodUsage<Self::Api>>) -> PeriodUsage<Self::Api> {
+        if mapper.is_empty() {
+            PeriodUsage {
+                deposited: BigUint::zero(),
+                lost: BigUint::zero(),
+                won: BigUint::zero(),
+            }
+        } else {
+            mapper.get()
+        }
+    }
+
+    fn empty_limits(&self) -> UserLimits<Self::Api> {
+        UserLimits {
+            daily_deposit_limit: BigUint::zero(),
+            weekly_deposit_limit: BigUint::zero(),
+            monthly_deposit_limit: BigUint::zero(),
+            daily_loss_limit: BigUint::zero(),
+            weekly_loss_limit: BigUint::zero(),
+            monthly_loss_limit: BigUint::zero(),
+            max_stake: BigUint::zero(),
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(getUserLimits)]
+    fn get_user_limits(&self, address: ManagedAddress, token: EgldOrEsdtTokenIdentifier) -> UserLimits<Self::Api> {
+        if self.user_limits(&address, &token).is_empty() {
+            return self.empty_limits();
+        }
+        self.user_limits(&address, &token).get()
+    }
+
+    #[view(getPendingUserLimits)]
+    fn get_pending_user_limits(
+        &self,
+        address: ManagedAddress,
+        token: EgldOrEsdtTokenIdentifier,
+    ) -> OptionalValue<PendingUserLimits<Self::Api>> {
+        if self.pending_user_limits(&address, &token).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.pending_user_limits(&address, &token).get())
+        }
+    }
+
+    /// Returns the most the address' open and unsettled bets in a token can still lose.
+    #[view(getOutstandingMaxLoss)]
+    fn get_outstanding_max_loss(&self, address: ManagedAddress, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.outstanding_max_loss(&address, &token).get()
+    }
+
+    #[view(getSelfExcludedUntil)]
+    fn get_self_excluded_until(&self, address: ManagedAddress) -> u64 {
+        self.self_excluded_until(&address).get()
+    }
+
+    /// Returns the address' deposits, losses and wins in the current period.
+    #[view(getUserPeriodUsage)]
+    fn get_user_period_usage(
+        &self,
+        address: ManagedAddress,
+        token: EgldOrEsdtTokenIdentifier,
+        period: LimitPeriod,
+    ) -> PeriodUsage<Self::Api> {
+        let mapper = self.user_period_usage(&address, &token, period, self.get_period_index(period));
+        self.get_usage_or_default(&mapper)
+    }
+}
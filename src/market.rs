@@ -1,4 +1,4 @@
-use crate::{errors::{ERR_MARKET_ALREADY_EXISTS, ERR_MARKET_NOT_OPEN, ERR_NO_MARKETS_FOUND}, types::{Market, MarketSelectionInfo, MarketStatus, MarketType, Selection, SelectionInfo, SelectionType, Sport, Tracker}};
+use crate::{constants::constants::{CLOSE_BUCKET_SECONDS, MAX_PAGE_SIZE, STORAGE_VERSION}, errors::{ERR_CANCELLATION_IN_PROGRESS, ERR_INVALID_MARKET, ERR_MARKET_ALREADY_EXISTS, ERR_MARKET_IN_PLAY, ERR_MARKET_NOT_CLOSED, ERR_MARKET_NOT_EXPIRED, ERR_MARKET_NOT_OPEN, ERR_MARKET_NOT_SUSPENDED, ERR_NOT_ODDS_ORACLE, ERR_NO_CANCELLATION_PENDING, ERR_NO_MARKETS_FOUND}, types::{sport_index, BetType, EventView, Market, MarketSelectionInfo, MarketStatus, MarketType, MarketView, ReferenceOdds, Role, Selection, SelectionInfo, SelectionState, SelectionType, SportId}};
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
@@ -6,6 +6,12 @@ multiversx_sc::derive_imports!();
 pub trait MarketModule:
     crate::storage::StorageModule +
     crate::events::EventsModule +
+    crate::roles::RolesModule +
+    crate::balance::BalanceModule +
+    crate::limits::LimitsModule +
+    crate::pause::PauseModule +
+    crate::sport::SportModule +
+    crate::sport_event::SportEventModule +
     crate::fund::FundModule + 
     crate::nft::NftModule +
     crate::orderbook::OrderbookModule +
@@ -16,19 +22,22 @@ pub trait MarketModule:
     //-------------------------------- Markets Creation -------------------------------------------//
     //--------------------------------------------------------------------------------------------//
 
-    /// Creates markets for a specific event and sport, restricted to the contract owner.
+    /// Creates the markets listed in the sport's templates for a registered event that is still
+    /// scheduled or live (market creator role).
     /// Parameters:
-    /// - sport: The type of sport (e.g., Football, Basketball).
+    /// - sport: The ID of an enabled sport from the sport registry.
     /// - event_id: The unique ID of the event.
     /// - close_timestamp: The timestamp when betting on the market closes.
-    #[only_owner]
     #[endpoint(createMarkets)]
     fn create_markets(
         &self,
-        sport: Sport,
+        sport: SportId,
         event_id: u64,
         close_timestamp: u64
     ){
+        self.require_role(Role::MarketCreator);
+        self.require_sport_enabled(sport);
+        self.require_event_open(sport, event_id);
         self.validate_market_creation(close_timestamp);
         
         let existing_markets = self.markets_by_event_and_sport(sport, event_id).get();
@@ -40,135 +49,60 @@ pub trait MarketModule:
         let mut market_ids = ManagedVec::new();
         let mut markets_info = ManagedVec::new();
         
-        // Create markets based on sport type
-        if sport == Sport::Football {
-            // Football specific markets
-            
-            // 1X2 market (Home, Draw, Away)
-            let ft_result_selections = [
-                SelectionType::One,
-                SelectionType::Draw,
-                SelectionType::Two
-            ];
-            
-            let (market_id_1x2, selections_1x2) = self.create_single_market(
+        // Create markets from the sport's templates
+        for template in self.sports(sport).get().market_templates.iter() {
+            let (market_id, selections) = self.create_single_market(
                 sport,
                 event_id,
-                &ft_result_selections,
+                template.market_type.selection_types(),
                 close_timestamp,
-                MarketType::FullTimeResult
+                template.market_type,
+                template.line
             );
-            market_ids.push(market_id_1x2);
+            market_ids.push(market_id);
             markets_info.push(MarketSelectionInfo {
-                market_id: market_id_1x2,
-                market_type: MarketType::FullTimeResult,
-                selections: selections_1x2
-            });
-            
-            // Over/Under market
-            let selection_types = [
-                SelectionType::Over,
-                SelectionType::Under
-            ];
-            let (market_id_ou, selections_ou) = self.create_single_market(
-                sport,
-                event_id,
-                &selection_types,
-                close_timestamp,
-                MarketType::TotalGoals
-            );
-            market_ids.push(market_id_ou);
-            markets_info.push(MarketSelectionInfo {
-                market_id: market_id_ou,
-                market_type: MarketType::TotalGoals,
-                selections: selections_ou
-            });
-            
-            // Both teams to score market
-            let selection_types = [
-                SelectionType::Yes,
-                SelectionType::No
-            ];
-            let (market_id_ggng, selections_ggng) = self.create_single_market(
-                sport,
-                event_id,
-                &selection_types,
-                close_timestamp,
-                MarketType::BothTeamsToScore
-            );
-            market_ids.push(market_id_ggng);
-            markets_info.push(MarketSelectionInfo {
-                market_id: market_id_ggng,
-                market_type: MarketType::BothTeamsToScore,
-                selections: selections_ggng
-            });
-        } else {
-            // For all other sports, create only Winner market
-            let winner_selections = [
-                SelectionType::One,
-                SelectionType::Two
-            ];
-            
-            let (market_id_winner, selections_winner) = self.create_single_market(
-                sport,
-                event_id,
-                &winner_selections,
-                close_timestamp,
-                MarketType::Winner
-            );
-            market_ids.push(market_id_winner);
-            markets_info.push(MarketSelectionInfo {
-                market_id: market_id_winner,
-                market_type: MarketType::Winner,
-                selections: selections_winner
+                market_id,
+                market_type: template.market_type,
+                selections
             });
         }
         
         self.markets_by_event_and_sport(sport, event_id).set(&market_ids);
-
-        let sport_index = match sport {
-            Sport::Football => 1u8,
-            Sport::Basketball => 2u8,
-            Sport::CounterStrike => 3u8,
-            Sport::Dota => 4u8,
-            Sport::LeagueOfLegends => 5u8,
-        };
         
-        self.create_market_event(sport_index, event_id, &markets_info);
+        self.create_market_event(sport_index(sport), event_id, &markets_info);
     }
 
 
     /// Creates a single market with specified selections for an event.
+    /// Market and selection IDs are issued from counters, and the market is registered under
+    /// (sport, event, market type, line) so the same market cannot be created twice. An issued ID
+    /// still held by a market created before the upgrade is rejected rather than overwritten.
     /// Parameters:
     /// - sport: The type of sport.
     /// - event_id: The unique ID of the event.
     /// - selection_types: Array of selection types for the market.
     /// - close_timestamp: The timestamp when the market closes.
     /// - market_type: The type of market (e.g., FullTimeResult, TotalGoals).
+    /// - line: The handicap or total line in tenths (e.g., 25 for 2.5 goals), or 0 if the market has none.
     /// Returns: A tuple containing the market ID and a vector of selection info.
     fn create_single_market(
         &self,
-        sport: Sport,
+        sport: SportId,
         event_id: u64,
         selection_types: &[SelectionType],
         close_timestamp: u64,
         market_type: MarketType,
+        line: u64,
     ) -> (u64, ManagedVec<Self::Api, SelectionInfo>) {
-        let market_type_index = match market_type {
-            MarketType::FullTimeResult => 1,
-            MarketType::TotalGoals => 2,
-            MarketType::BothTeamsToScore => 3,
-            MarketType::Winner => 4,
-        };
-        
-        let sport_index = match sport {
-            Sport::Football => 1,
-            Sport::Basketball => 2,
-            Sport::CounterStrike => 3,
-            Sport::Dota => 4,
-            Sport::LeagueOfLegends => 5,
-        };
-        let market_id = (sport_index * 1_000_000) + (event_id * 1000) + market_type_index;
+        let lookup = self.market_id_lookup(sport, event_id, market_type, line);
+        require!(lookup.is_empty(), ERR_MARKET_ALREADY_EXISTS);
+
+        let market_id = self.next_market_id().get() + 1;
+        require!(self.markets(market_id).is_empty(), ERR_MARKET_ALREADY_EXISTS);
+        self.next_market_id().set(market_id);
+        lookup.set(market_id);
+        self.market_line(market_id).set(line);
+        self.market_storage_version(market_id).set(STORAGE_VERSION);
 
         let selections = self.create_selections(market_id, selection_types);
         
@@ -176,7 +110,7 @@ pub trait MarketModule:
             market_id,
             event_id,
             market_type,
-            description: ManagedBuffer::new_from_bytes(market_type.to_description()),
+            description: self.market_description(market_type, line),
             selections: selections.clone(),
             liquidity: BigUint::zero(),
             close_timestamp,
@@ -186,19 +120,21 @@ pub trait MarketModule:
         };
 
         self.markets(market_id).set(&market);
+        self.market_sport_index(market_id).set(sport_index(sport));
+        self.index_open_market(market_id, sport, close_timestamp);
 
         let mut selection_infos = ManagedVec::new();
-        for (index, selection_type) in selection_types.iter().enumerate() {
+        for selection in selections.iter() {
             selection_infos.push(SelectionInfo {
-                selection_id: (index + 1) as u64,
-                selection_type: *selection_type,
+                selection_id: selection.id,
+                selection_type: selection.selection_type,
             });
         }
 
         (market_id, selection_infos)
     }
 
-    /// Creates selections for a market based on provided selection types.
+    /// Creates selections for a market based on provided selection types, issuing their IDs from a counter.
     /// Parameters:
     /// - market_id: The ID of the market.
     /// - selection_types: Array of selection types to create.
@@ -207,18 +143,18 @@ pub trait MarketModule:
         &self,
         market_id: u64,
         selection_types: &[SelectionType],
-    ) -> ManagedVec<Selection<Self::Api>> {
+    ) -> ManagedVec<Self::Api, Selection> {
         let mut selections = ManagedVec::new();
-        for (index, selection_type) in selection_types.iter().enumerate() {
-            let id = market_id * 10 + (index + 1) as u64;
+        let mut id = self.next_selection_id().get();
+        for selection_type in selection_types.iter() {
+            id += 1;
             self.init_selection_storage(market_id, id);
-            let tracker = self.selection_tracker(market_id, id).get();
             selections.push(Selection { 
                 id,
                 selection_type: *selection_type,
-                priority_queue: tracker,
             });
         }
+        self.next_selection_id().set(id);
         selections
     }
 
@@ -230,9 +166,9 @@ pub trait MarketModule:
     /// Parameters:
     /// - sport: The type of sport.
     /// - event_id: The unique ID of the event.
-    #[only_owner]
     #[endpoint(closeMarkets)]
-    fn close_markets(&self, sport: Sport, event_id: u64) {
+    fn close_markets(&self, sport: SportId, event_id: u64) {
+        self.require_role(Role::Resolver);
         let market_ids = self.markets_by_event_and_sport(sport, event_id).get();
         require!(
             !market_ids.is_empty(),
@@ -240,46 +176,233 @@ pub trait MarketModule:
         );
     
         for market_id in market_ids.iter() {
-            let mut market = self.markets(market_id).get();
+            let market = self.markets(market_id).get();
             require!(
-                market.market_status == MarketStatus::Open,
+                market.market_status == MarketStatus::Open || market.market_status == MarketStatus::Suspended,
                ERR_MARKET_NOT_OPEN
             );
-            self.handle_expired_market(sport, event_id, market_id);
-            market.market_status = MarketStatus::Closed;
-            self.markets(market_id).set(&market);
+            self.handle_expired_market(market_id);
         }
     }
 
+    /// Closes a market whose close_timestamp has passed, callable by anyone.
+    /// In-play markets keep trading after close_timestamp and can only be closed by a resolver.
+    /// If a keeper reward is configured and funded, it is paid to the caller in EGLD.
+    /// Parameters:
+    /// - market_id: The ID of the market to close.
+    #[endpoint(closeExpiredMarket)]
+    fn close_expired_market(&self, market_id: u64) {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let market = self.markets(market_id).get();
+        require!(
+            market.market_status == MarketStatus::Open || market.market_status == MarketStatus::Suspended,
+            ERR_MARKET_NOT_OPEN
+        );
+        require!(
+            self.blockchain().get_block_timestamp() >= market.close_timestamp,
+            ERR_MARKET_NOT_EXPIRED
+        );
+        require!(!self.market_in_play(market_id).get(), ERR_MARKET_IN_PLAY);
+
+        self.handle_expired_market(market_id);
+        self.pay_keeper_reward();
+    }
+
+    /// Refunds the unmatched part of up to `max_bets` bets of a closed market, callable by anyone.
+    /// Parameters:
+    /// - market_id: The ID of the closed market.
+    /// - max_bets: The maximum number of bets to process in this call.
+    /// Returns: The number of bets still waiting to be processed.
+    #[endpoint(refundUnmatchedBatch)]
+    fn refund_unmatched_batch(&self, market_id: u64, max_bets: usize) -> usize {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        self.require_market_closed_or_settled(market_id);
+
+        self.process_refund_batch(market_id, max_bets)
+    }
+
+    /// Refunds the unmatched part of a single bet of a closed market, callable by anyone.
+    /// The refund always goes to the bettor.
+    /// Parameters:
+    /// - bet_id: The ID of the bet to refund.
+    #[endpoint(refundUnmatched)]
+    fn refund_unmatched(&self, bet_id: u64) {
+        let bet = self.get_bet(bet_id);
+        self.require_market_closed_or_settled(bet.event);
+
+        self.return_unmatched_amount(bet_id);
+    }
+
+    /// Sets the EGLD reward paid to whoever closes an expired market (treasury role).
+    /// Parameters:
+    /// - reward: The reward per closed market (zero disables rewards).
+    #[endpoint(setKeeperReward)]
+    fn set_keeper_reward(&self, reward: BigUint) {
+        self.require_role(Role::Treasury);
+        self.require_not_timelocked();
+        self.keeper_reward().set(&reward);
+    }
+
+    /// Adds the attached EGLD to the pool that keeper rewards are paid from.
+    #[payable("EGLD")]
+    #[endpoint(fundKeeperRewards)]
+    fn fund_keeper_rewards(&self) {
+        let payment = self.call_value().egld_value().clone_value();
+        self.keeper_reward_pool().update(|pool| *pool += &payment);
+    }
+
     //--------------------------------------------------------------------------------------------//
-    //-------------------------------- Helper Functions ------------------------------------------//
+    //-------------------------------- In-Play Trading -------------------------------------------//
     //--------------------------------------------------------------------------------------------//
 
-    /// Initializes storage for a selection in a market.
+    /// Configures in-play trading for a market (market creator role).
+    /// An in-play market stays open after its close_timestamp (kickoff) and every bet placed
+    /// after kickoff is held for `bet_delay` seconds before it is matched.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - enabled: Whether the market keeps trading after kickoff.
+    /// - bet_delay: The number of seconds an in-play bet is held before matching.
+    #[endpoint(setInPlay)]
+    fn set_in_play(&self, market_id: u64, enabled: bool, bet_delay: u64) {
+        self.require_role(Role::MarketCreator);
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let market = self.markets(market_id).get();
+        require!(
+            market.market_status == MarketStatus::Open || market.market_status == MarketStatus::Suspended,
+            ERR_MARKET_NOT_OPEN
+        );
+
+        self.market_in_play(market_id).set(enabled);
+        self.market_bet_delay(market_id).set(bet_delay);
+    }
+
+    /// Suspends an open market (e.g. on a goal, a red card or breaking news) (risk manager role).
+    /// New bets and cancellations are rejected while resting orders stay in the book,
+    /// unless `cancel_unmatched` is set, in which case every unmatched order is scheduled for
+    /// cancellation; the refunds are then paid in batches via cancelUnmatchedBatch.
+    /// Parameters:
+    /// - market_id: The ID of the market to suspend.
+    /// - cancel_unmatched: Whether to cancel and refund all unmatched orders.
+    #[endpoint(suspendMarket)]
+    fn suspend_market(&self, market_id: u64, cancel_unmatched: bool) {
+        self.require_role(Role::RiskManager);
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let market = self.markets(market_id).get();
+        require!(market.market_status == MarketStatus::Open, ERR_MARKET_NOT_OPEN);
+
+        self.suspend_open_market(market_id, cancel_unmatched);
+    }
+
+    /// Cancels the unmatched part of up to `max_bets` bets of a market suspended with
+    /// `cancel_unmatched`, callable by anyone. The market can only resume once every bet was processed.
+    /// Parameters:
+    /// - market_id: The ID of the suspended market.
+    /// - max_bets: The maximum number of bets to process in this call.
+    /// Returns: The number of bets still waiting to be processed.
+    #[endpoint(cancelUnmatchedBatch)]
+    fn cancel_unmatched_batch(&self, market_id: u64, max_bets: usize) -> usize {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        require!(!self.cancel_cursor(market_id).is_empty(), ERR_NO_CANCELLATION_PENDING);
+
+        self.process_cancel_batch(market_id, max_bets)
+    }
+
+    /// Reopens a suspended market for betting (risk manager role).
+    /// Fails while a cancellation of its unmatched orders is still in progress.
+    /// Parameters:
+    /// - market_id: The ID of the market to resume.
+    #[endpoint(resumeMarket)]
+    fn resume_market(&self, market_id: u64) {
+        self.require_role(Role::RiskManager);
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let mut market = self.markets(market_id).get();
+        require!(market.market_status == MarketStatus::Suspended, ERR_MARKET_NOT_SUSPENDED);
+        require!(self.cancel_cursor(market_id).is_empty(), ERR_CANCELLATION_IN_PROGRESS);
+
+        market.market_status = MarketStatus::Open;
+        self.markets(market_id).set(&market);
+
+        self.market_resumed_event(market_id, self.blockchain().get_block_timestamp());
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Reference Prices ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Sets the address allowed to push reference odds besides resolvers (admin role).
+    /// Parameters:
+    /// - oracle: The oracle address.
+    #[endpoint(setOddsOracle)]
+    fn set_odds_oracle(&self, oracle: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        self.odds_oracle().set(&oracle);
+    }
+
+    /// Sets the fair odds of a selection, as priced by a resolver or the odds oracle.
     /// Parameters:
     /// - market_id: The ID of the market.
     /// - selection_id: The ID of the selection.
-    fn init_selection_storage(&self, market_id: u64, selection_id: u64) {
-        let tracker = Tracker {
-            back_levels: ManagedVec::new(),
-            lay_levels: ManagedVec::new(),
-            back_liquidity: BigUint::zero(),
-            lay_liquidity: BigUint::zero(),
-            matched_count: 0,
-            unmatched_count: 0,
-            partially_matched_count: 0,
-            win_count: 0,
-            lost_count: 0,
-            canceled_count: 0,
-        };
+    /// - odds: The reference odds (e.g., 250 for 2.50).
+    #[endpoint(setReferenceOdds)]
+    fn set_reference_odds(&self, market_id: u64, selection_id: u64, odds: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let is_oracle = !self.odds_oracle().is_empty() && self.odds_oracle().get() == caller;
+        require!(
+            self.has_role_internal(Role::Resolver, &caller) || is_oracle,
+            ERR_NOT_ODDS_ORACLE
+        );
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        self.validate_selection(market_id, selection_id);
+        self.validate_bet_odds(&odds);
+
+        let timestamp = self.blockchain().get_block_timestamp();
+        self.reference_odds(market_id, selection_id).set(&ReferenceOdds {
+            odds: odds.clone(),
+            updated_at: timestamp,
+        });
+        self.reference_odds_updated_event(market_id, selection_id, &odds, timestamp);
+    }
+
+    /// Sets how far, in basis points, order odds may deviate from the reference odds of a market (risk manager role).
+    /// Orders on selections without reference odds are not checked, and zero disables the check.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - max_deviation_bps: The maximum deviation in basis points.
+    #[endpoint(setMaxOddsDeviation)]
+    fn set_max_odds_deviation(&self, market_id: u64, max_deviation_bps: u64) {
+        self.require_role(Role::RiskManager);
+        self.require_not_timelocked();
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        self.max_odds_deviation_bps(market_id).set(max_deviation_bps);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
 
-        self.selection_tracker(market_id, selection_id).set(&tracker);
+    /// Returns the description of a market, showing the line of TotalGoals markets.
+    /// Parameters:
+    /// - market_type: The type of the market.
+    /// - line: The line of the market in tenths.
+    fn market_description(&self, market_type: MarketType, line: u64) -> ManagedBuffer {
+        if market_type != MarketType::TotalGoals {
+            return ManagedBuffer::new_from_bytes(market_type.to_description());
+        }
 
-        self.selection_back_levels(market_id, selection_id)
-            .set(&ManagedVec::new());
-        self.selection_lay_levels(market_id, selection_id)
-            .set(&ManagedVec::new());
+        let mut description = ManagedBuffer::new_from_bytes(b"Over/Under ");
+        description.append(&self.u64_to_ascii(line / 10));
+        description.append_bytes(b".");
+        description.append(&self.u64_to_ascii(line % 10));
+        description.append_bytes(b" Goals");
+        description
+    }
 
+    /// Initializes storage for a selection in a market.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    fn init_selection_storage(&self, market_id: u64, selection_id: u64) {
         self.selection_back_liquidity(market_id, selection_id)
             .set(&BigUint::zero());
         self.selection_lay_liquidity(market_id, selection_id)
@@ -290,11 +413,34 @@ pub trait MarketModule:
         self.selection_partially_matched_count(market_id, selection_id).set(&0u64);
         self.selection_win_count(market_id, selection_id).set(&0u64);
         self.selection_lost_count(market_id, selection_id).set(&0u64);
+        self.selection_void_count(market_id, selection_id).set(0u64);
         self.selection_canceled_count(market_id, selection_id).set(&0u64);
 
         self.total_matched_amount(market_id, selection_id).set(&BigUint::zero());
     }
 
+    /// Pays the configured keeper reward to the caller if the reward pool can cover it.
+    fn pay_keeper_reward(&self) {
+        let reward = self.keeper_reward().get();
+        if reward == BigUint::zero() || self.keeper_reward_pool().get() < reward {
+            return;
+        }
+
+        self.keeper_reward_pool().update(|pool| *pool -= &reward);
+        self.send().direct_egld(&self.blockchain().get_caller(), &reward);
+    }
+
+    /// Fails unless the market has stopped trading.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn require_market_closed_or_settled(&self, market_id: u64) {
+        let status = self.markets(market_id).get().market_status;
+        require!(
+            status == MarketStatus::Closed || status == MarketStatus::Settled,
+            ERR_MARKET_NOT_CLOSED
+        );
+    }
+
     /// Retrieves a specific selection from a market.
     /// Parameters:
     /// - market: The market object containing selections.
@@ -304,12 +450,74 @@ pub trait MarketModule:
         &self,
         market: &Market<Self::Api>,
         selection_id: u64
-    ) -> Selection<Self::Api> {
+    ) -> Selection {
         market.selections.iter()
             .find(|s| s.id == selection_id)
             .unwrap_or_else(|| sc_panic!(crate::errors::ERR_INVALID_SELECTION))
     }
 
+    /// Builds the state of a selection from its book and counters, the single place that state is kept.
+    /// The counters hold the number of bets currently in each status; a bet cancelled or refunded
+    /// without any matched part is counted as canceled.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - selection_type: The type of the selection.
+    fn build_selection_state(
+        &self,
+        market_id: u64,
+        selection_id: u64,
+        selection_type: SelectionType
+    ) -> SelectionState<Self::Api> {
+        SelectionState {
+            selection_id,
+            selection_type,
+            back_liquidity: self.selection_back_liquidity(market_id, selection_id).get(),
+            lay_liquidity: self.selection_lay_liquidity(market_id, selection_id).get(),
+            matched_amount: self.total_matched_amount(market_id, selection_id).get(),
+            best_back_odds: self.get_best_odds(market_id, selection_id, BetType::Back),
+            best_lay_odds: self.get_best_odds(market_id, selection_id, BetType::Lay),
+            back_price_levels: self.price_levels(market_id, selection_id, BetType::Back).len() as u32,
+            lay_price_levels: self.price_levels(market_id, selection_id, BetType::Lay).len() as u32,
+            unmatched_count: self.selection_unmatched_count(market_id, selection_id).get(),
+            partially_matched_count: self.selection_partially_matched_count(market_id, selection_id).get(),
+            matched_count: self.selection_matched_count(market_id, selection_id).get(),
+            win_count: self.selection_win_count(market_id, selection_id).get(),
+            lost_count: self.selection_lost_count(market_id, selection_id).get(),
+            void_count: self.selection_void_count(market_id, selection_id).get(),
+            canceled_count: self.selection_canceled_count(market_id, selection_id).get(),
+        }
+    }
+
+    /// Builds the market DTO returned by the views, with live liquidity and best prices per selection.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn build_market_view(&self, market_id: u64) -> MarketView<Self::Api> {
+        let market = self.markets(market_id).get();
+
+        let mut selections = ManagedVec::new();
+        for selection in market.selections.iter() {
+            selections.push(self.build_selection_state(market_id, selection.id, selection.selection_type));
+        }
+
+        MarketView {
+            market_id,
+            sport: self.require_market_sport(market_id),
+            event_id: market.event_id,
+            market_type: market.market_type,
+            description: market.description,
+            line: self.resolve_market_line(market_id),
+            status: market.market_status,
+            in_play: self.market_in_play(market_id).get(),
+            close_timestamp: market.close_timestamp,
+            total_matched_amount: market.total_matched_amount,
+            winning_selection: self.winning_selection(market_id).get(),
+            is_void: self.market_void(market_id).get(),
+            selections,
+            created_at: market.created_at,
+        }
+    }
+
     //--------------------------------------------------------------------------------------------//
     //-------------------------------- View Functions --------------------------------------------//
     //--------------------------------------------------------------------------------------------//
@@ -319,9 +527,226 @@ pub trait MarketModule:
         self.markets(market_id).get().market_status
     }
 
+    #[view(isMarketInPlay)]
+    fn is_market_in_play(&self, market_id: u64) -> bool {
+        self.market_in_play(market_id).get()
+    }
+
+    #[view(getMarketBetDelay)]
+    fn get_market_bet_delay(&self, market_id: u64) -> u64 {
+        self.market_bet_delay(market_id).get()
+    }
+
+    #[view(getDelayedBetsCount)]
+    fn get_delayed_bets_count(&self, market_id: u64) -> usize {
+        self.delayed_bets(market_id).len()
+    }
+
+    #[view(getRefundCursor)]
+    fn get_refund_cursor(&self, market_id: u64) -> usize {
+        self.refund_cursor(market_id).get()
+    }
+
+    /// Returns the settlement progress of a market as (bets left to settle, result set or voided, fully settled).
+    #[view(getSettlementProgress)]
+    fn get_settlement_progress(&self, market_id: u64) -> MultiValue3<usize, bool, bool> {
+        let result_set = !self.winning_selection(market_id).is_empty() || self.market_void(market_id).get();
+        let settled = self.markets(market_id).get().market_status == MarketStatus::Settled;
+        (self.settlement_cursor(market_id).get(), result_set, settled).into()
+    }
+
+    /// Returns the ID of the market registered for (sport, event, market type, line), or 0 if none.
+    #[view(getMarketId)]
+    fn get_market_id(&self, sport: SportId, event_id: u64, market_type: MarketType, line: u64) -> u64 {
+        self.market_id_lookup(sport, event_id, market_type, line).get()
+    }
+
+    #[view(getMarketLine)]
+    fn get_market_line(&self, market_id: u64) -> u64 {
+        self.resolve_market_line(market_id)
+    }
+
+    #[view(getEventScore)]
+    fn get_event_score(&self, sport: SportId, event_id: u64) -> MultiValue2<u32, u32> {
+        self.find_event_score(sport, event_id).unwrap_or_default().into()
+    }
+
+    #[view(isMarketVoid)]
+    fn is_market_void(&self, market_id: u64) -> bool {
+        self.market_void(market_id).get()
+    }
+
+    /// Returns the prices of a selection as (reference odds, reference update timestamp, best back odds, best lay odds).
+    /// Zero values mean no reference price or no resting order on that side.
+    #[view(getSelectionPrices)]
+    fn get_selection_prices(
+        &self,
+        market_id: u64,
+        selection_id: u64,
+    ) -> MultiValue4<BigUint, u64, BigUint, BigUint> {
+        let (reference_odds, updated_at) = if self.reference_odds(market_id, selection_id).is_empty() {
+            (BigUint::zero(), 0u64)
+        } else {
+            let reference = self.reference_odds(market_id, selection_id).get();
+            (reference.odds, reference.updated_at)
+        };
+
+        let best_back = self.get_best_odds(market_id, selection_id, BetType::Back);
+        let best_lay = self.get_best_odds(market_id, selection_id, BetType::Lay);
+        (reference_odds, updated_at, best_back, best_lay).into()
+    }
+
+    #[view(getMaxOddsDeviation)]
+    fn get_max_odds_deviation(&self, market_id: u64) -> u64 {
+        self.max_odds_deviation_bps(market_id).get()
+    }
+
+    #[view(getKeeperReward)]
+    fn get_keeper_reward(&self) -> BigUint {
+        self.keeper_reward().get()
+    }
+
+    #[view(getWinningSelection)]
+    fn get_winning_selection(&self, market_id: u64) -> OptionalValue<u64> {
+        if self.winning_selection(market_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.winning_selection(market_id).get())
+        }
+    }
+
+    #[view(getMarket)]
+    fn get_market(&self, market_id: u64) -> MarketView<Self::Api> {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        self.build_market_view(market_id)
+    }
+
+    #[view(getSelectionState)]
+    fn get_selection_state(&self, market_id: u64, selection_id: u64) -> SelectionState<Self::Api> {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        let selection = self.get_selection(&self.markets(market_id).get(), selection_id);
+        self.build_selection_state(market_id, selection.id, selection.selection_type)
+    }
+
+    #[view(getEventMarkets)]
+    fn get_event_markets(&self, sport: SportId, event_id: u64) -> MultiValueEncoded<MarketView<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for market_id in self.markets_by_event_and_sport(sport, event_id).get().iter() {
+            result.push(self.build_market_view(market_id));
+        }
+        result
+    }
+
+    /// Returns the event together with its score, if set, and the IDs of its markets.
+    #[view(getEventDetails)]
+    fn get_event_details(&self, sport: SportId, event_id: u64) -> EventView<Self::Api> {
+        let score = self.find_event_score(sport, event_id);
+        let (score_home, score_away) = score.unwrap_or_default();
+        EventView {
+            event: self.require_event(sport, event_id),
+            has_score: score.is_some(),
+            score_home,
+            score_away,
+            market_ids: self.markets_by_event_and_sport(sport, event_id).get(),
+        }
+    }
+
+    /// Returns a page of the markets of a sport that are not closed yet (open or suspended).
+    /// Closing a market reorders the index, so clients should page from a fresh start after changes.
+    /// Parameters:
+    /// - sport: The sport.
+    /// - from: The zero-based offset of the first market.
+    /// - size: The page size, capped at MAX_PAGE_SIZE.
+    #[view(getOpenMarkets)]
+    fn get_open_markets(&self, sport: SportId, from: usize, size: usize) -> MultiValueEncoded<MarketView<Self::Api>> {
+        let index = self.open_markets_by_sport(sport);
+        let end = core::cmp::min(index.len(), from.saturating_add(core::cmp::min(size, MAX_PAGE_SIZE)));
+
+        let mut result = MultiValueEncoded::new();
+        for position in from..end {
+            result.push(self.build_market_view(index.get_by_index(position + 1)));
+        }
+        result
+    }
+
+    #[view(getOpenMarketsCount)]
+    fn get_open_markets_count(&self, sport: SportId) -> usize {
+        self.open_markets_by_sport(sport).len()
+    }
+
+    /// Returns a page of the markets not closed yet whose close time is before the given timestamp,
+    /// preceded by the cursor to pass for the next page. The cursor is a (bucket, market) position in
+    /// the close-time index, so each page only reads the index from where the previous one stopped.
+    /// Closing a market reorders the index, so clients should page from (0, 0) again after changes.
+    /// Parameters:
+    /// - timestamp: The upper bound (exclusive) of the close time.
+    /// - bucket_from: The zero-based position of the close-time bucket to start from.
+    /// - market_from: The zero-based position of the market to start from within that bucket.
+    /// - size: The page size, capped at MAX_PAGE_SIZE.
+    /// Returns: The next (bucket, market) cursor, whose bucket position is the bucket count once
+    /// every market was returned, followed by the markets of the page.
+    #[view(getMarketsClosingBefore)]
+    fn get_markets_closing_before(
+        &self,
+        timestamp: u64,
+        bucket_from: usize,
+        market_from: usize,
+        size: usize,
+    ) -> MultiValue3<usize, usize, MultiValueEncoded<MarketView<Self::Api>>> {
+        let last_bucket = timestamp / CLOSE_BUCKET_SECONDS;
+        let page_size = core::cmp::min(size, MAX_PAGE_SIZE);
+        let buckets = self.open_close_buckets();
+        let bucket_count = buckets.len();
+
+        let mut page = MultiValueEncoded::new();
+        let mut bucket_position = bucket_from;
+        let mut market_position = market_from;
+        while bucket_position < bucket_count {
+            let bucket = buckets.get_by_index(bucket_position + 1);
+            if bucket <= last_bucket {
+                let markets = self.open_markets_by_close_bucket(bucket);
+                while market_position < markets.len() {
+                    if page.len() == page_size {
+                        return (bucket_position, market_position, page).into();
+                    }
+                    let market_id = markets.get_by_index(market_position + 1);
+                    market_position += 1;
+                    if self.markets(market_id).get().close_timestamp < timestamp {
+                        page.push(self.build_market_view(market_id));
+                    }
+                }
+            }
+            bucket_position += 1;
+            market_position = 0;
+        }
+        (bucket_count, 0, page).into()
+    }
+
+    /// Returns a page of settled markets in settlement order.
+    /// Parameters:
+    /// - from: The zero-based offset of the first market.
+    /// - size: The page size, capped at MAX_PAGE_SIZE.
+    #[view(getSettledMarkets)]
+    fn get_settled_markets(&self, from: usize, size: usize) -> MultiValueEncoded<MarketView<Self::Api>> {
+        let index = self.settled_markets();
+        let end = core::cmp::min(index.len(), from.saturating_add(core::cmp::min(size, MAX_PAGE_SIZE)));
+
+        let mut result = MultiValueEncoded::new();
+        for position in from..end {
+            result.push(self.build_market_view(index.get(position + 1)));
+        }
+        result
+    }
+
+    #[view(getSettledMarketsCount)]
+    fn get_settled_markets_count(&self) -> usize {
+        self.settled_markets().len()
+    }
+
+    /// Returns true once every market of the event is closed or settled.
     #[view(areEventMarketsClosed)]
-    fn are_event_markets_closed(&self, event_id: u64) -> bool {
-        let market_ids = self.markets_by_event(event_id).get();
+    fn are_event_markets_closed(&self, sport: SportId, event_id: u64) -> bool {
+        let market_ids = self.markets_by_event_and_sport(sport, event_id).get();
         require!(
             !market_ids.is_empty(),
             ERR_NO_MARKETS_FOUND
@@ -329,11 +754,11 @@ pub trait MarketModule:
 
         for market_id in market_ids.iter() {
             let market = self.markets(market_id).get();
-            if market.market_status != MarketStatus::Closed {
+            if market.market_status != MarketStatus::Closed && market.market_status != MarketStatus::Settled {
                 return false;
             }
         }
 
         true
     }
-}
\ No newline at end of file
+}
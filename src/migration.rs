@@ -0,0 +1,256 @@
+use crate::{
+    constants::constants::STORAGE_VERSION,
+    errors::ERR_INVALID_MARKET,
+    types::{sport_index, BetStatus, BetType, LegacyPriceLevel, MarketStatus, Role}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait MigrationModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::balance::BalanceModule
+    + crate::limits::LimitsModule
+    + crate::orderbook::OrderbookModule
+    + crate::utils::UtilsModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Migrations ------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Rewrites a market and its bets in the current encodings (admin role), and moves the orders
+    /// resting in its legacy per-selection levels into the FIFO queues. Resumable: each call handles
+    /// up to `max_bets` bets, first the legacy orders in their queue order, then every bet of the
+    /// market, walking `market_bet_ids` from the last index down. Migrated markets are skipped.
+    /// Markets still open at upgrade take no new bets until migrated, since their legacy orders only
+    /// join the book here and the liquidity of their sides is summed again from those orders.
+    /// Parameters:
+    /// - market_id: The ID of the market to migrate.
+    /// - max_bets: The maximum number of bets to handle in this call.
+    /// Returns: The number of bets of the market left to rewrite, at least one while legacy orders
+    /// are still waiting to join the book.
+    #[endpoint(migrateBatch)]
+    fn migrate_batch(&self, market_id: u64, max_bets: usize) -> usize {
+        self.require_role(Role::Admin);
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        if self.market_storage_version(market_id).get() >= STORAGE_VERSION {
+            return 0;
+        }
+
+        let bet_ids = self.market_bet_ids(market_id);
+        if !self.migration_started(market_id).get() {
+            self.migrate_market_record(market_id);
+            self.migration_cursor(market_id).set(bet_ids.len());
+            self.migration_started(market_id).set(true);
+        }
+
+        let (mut processed, books_rebuilt) = self.rebuild_legacy_books(market_id, max_bets);
+        let mut cursor = self.migration_cursor(market_id).get();
+        if !books_rebuilt {
+            return core::cmp::max(cursor, 1);
+        }
+
+        while cursor > 0 && processed < max_bets {
+            if cursor <= bet_ids.len() {
+                let bet_id = bet_ids.get_by_index(cursor);
+                if !self.bet_by_id(bet_id).is_empty() {
+                    let bet = self.bet_by_id(bet_id).get();
+                    self.bet_by_id(bet_id).set(&bet);
+                }
+            }
+            cursor -= 1;
+            processed += 1;
+        }
+
+        if cursor == 0 {
+            self.migration_cursor(market_id).clear();
+            self.migration_started(market_id).clear();
+            self.market_storage_version(market_id).set(STORAGE_VERSION);
+            self.market_migrated_event(market_id, STORAGE_VERSION);
+        } else {
+            self.migration_cursor(market_id).set(cursor);
+        }
+        cursor
+    }
+
+    /// Drains the legacy bet hash index (admin role). Before the upgrade it mapped hashes to IDs only,
+    /// so deleting a bet could not find its entry; each call moves up to `max_entries` entries of
+    /// bets that still exist to the current index with their reverse entry, and drops the others.
+    /// Parameters:
+    /// - max_entries: The maximum number of entries to process in this call.
+    /// Returns: The number of legacy entries left.
+    #[endpoint(migrateBetHashes)]
+    fn migrate_bet_hashes(&self, max_entries: usize) -> usize {
+        self.require_role(Role::Admin);
+
+        let mut legacy_hashes = self.legacy_bet_hash_to_id();
+        let batch = legacy_hashes.keys().take(max_entries).collect::<ManagedVec<ManagedBuffer>>();
+        for bet_hash in batch.iter() {
+            let bet_id = legacy_hashes.remove(&bet_hash).unwrap_or_default();
+            if !self.bet_by_id(bet_id).is_empty() {
+                self.bet_hash_to_id().insert(bet_hash.clone_value(), bet_id);
+                self.bet_id_to_hash(bet_id).set(&*bet_hash);
+            }
+        }
+
+        legacy_hashes.len()
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Brings the storage version up to STORAGE_VERSION. Called on upgrade.
+    /// Global steps run here and must stay cheap enough for the upgrade transaction; work that grows
+    /// with the number of markets or bets goes through migrateBatch instead.
+    fn migrate_storage(&self) {
+        let from_version = self.storage_version().get();
+        if from_version >= STORAGE_VERSION {
+            return;
+        }
+
+        // Version 1 versions the Bet and Market encodings. Legacy records stay readable and are
+        // rewritten market by market through migrateBatch, so it has no global step.
+        // Later versions add their global steps here, guarded by `from_version < N`.
+
+        self.storage_version().set(STORAGE_VERSION);
+        self.storage_migrated_event(from_version, STORAGE_VERSION);
+    }
+
+    /// Rewrites a market record and backfills what markets created before the upgrade lack: the
+    /// line of TotalGoals markets, the stored sport, the (sport, event, type, line) lookup, the event
+    /// score under the per-sport key and, for markets not closed yet, the open-market indexes.
+    /// The market ID counter is moved past the market's ID, so new markets are issued above it.
+    /// The liquidity of the sides that still have legacy levels is dropped, to be summed again from
+    /// the orders moved by rebuild_legacy_books. The cost only grows with the number of selections.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn migrate_market_record(&self, market_id: u64) {
+        let market = self.markets(market_id).get();
+        self.markets(market_id).set(&market);
+
+        if market_id > self.next_market_id().get() {
+            // Legacy IDs were derived from the sport and event, so new IDs must be issued above them
+            self.next_market_id().set(market_id);
+        }
+
+        let line = self.resolve_market_line(market_id);
+        self.market_line(market_id).set(line);
+        if let Some(sport) = self.find_market_sport(market_id) {
+            if self.market_sport_index(market_id).is_empty() {
+                self.market_sport_index(market_id).set(sport_index(sport));
+            }
+            let lookup = self.market_id_lookup(sport, market.event_id, market.market_type, line);
+            if lookup.is_empty() {
+                lookup.set(market_id);
+            }
+            if self.event_score(sport, market.event_id).is_empty() && !self.legacy_event_score(market.event_id).is_empty() {
+                self.event_score(sport, market.event_id).set(self.legacy_event_score(market.event_id).get());
+            }
+            let not_closed = market.market_status == MarketStatus::Open || market.market_status == MarketStatus::Suspended;
+            if not_closed && !self.open_markets_by_sport(sport).contains(&market_id) {
+                self.index_open_market(market_id, sport, market.close_timestamp);
+            }
+        }
+
+        for selection in market.selections.iter() {
+            for bet_type in [BetType::Back, BetType::Lay] {
+                if !self.legacy_levels(market_id, selection.id, bet_type).is_empty() {
+                    self.side_liquidity(market_id, selection.id, bet_type).clear();
+                }
+            }
+        }
+    }
+
+    /// Moves up to `max_bets` orders from the legacy per-selection levels of a market into the FIFO
+    /// queues, best price first and in their original queue order, folding their escrow out of the
+    /// legacy address-wide locked funds. Orders that no longer rest are dropped. Levels are consumed
+    /// as they are moved, so a later call resumes where this one stopped.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - max_bets: The maximum number of orders to handle.
+    /// Returns: The number of orders handled, and whether every legacy level of the market is gone.
+    fn rebuild_legacy_books(&self, market_id: u64, max_bets: usize) -> (usize, bool) {
+        let market = self.markets(market_id).get();
+        let mut processed = 0usize;
+
+        for selection in market.selections.iter() {
+            for bet_type in [BetType::Back, BetType::Lay] {
+                let legacy_levels = self.legacy_levels(market_id, selection.id, bet_type);
+                if legacy_levels.is_empty() {
+                    continue;
+                }
+
+                let mut levels = legacy_levels.get();
+                while !levels.is_empty() {
+                    let mut level = levels.get(0);
+                    while !level.bet_ids.is_empty() {
+                        if processed == max_bets {
+                            let _ = levels.set(0, level);
+                            legacy_levels.set(&levels);
+                            return (processed, false);
+                        }
+                        let bet_id = level.bet_ids.get(0);
+                        level.bet_ids.remove(0);
+                        self.move_legacy_order(bet_id);
+                        processed += 1;
+                    }
+                    levels.remove(0);
+                }
+                legacy_levels.clear();
+            }
+            self.legacy_selection_tracker(market_id, selection.id).clear();
+        }
+        (processed, true)
+    }
+
+    /// Adds a bet found in a legacy level to the order book if it still rests there.
+    /// Parameters:
+    /// - bet_id: The ID of the bet.
+    fn move_legacy_order(&self, bet_id: u64) {
+        if self.bet_by_id(bet_id).is_empty() {
+            return;
+        }
+        let bet = self.bet_by_id(bet_id).get();
+        if bet.status == BetStatus::Unmatched || bet.status == BetStatus::PartiallyMatched {
+            // Before add_to_orderbook indexes the bet as escrowed
+            self.fold_legacy_escrow(&bet);
+            self.add_to_orderbook(&bet);
+        }
+    }
+
+    /// Returns the legacy levels of one side of a selection.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - bet_type: The side of the book.
+    fn legacy_levels(
+        &self,
+        market_id: u64,
+        selection_id: u64,
+        bet_type: BetType,
+    ) -> SingleValueMapper<ManagedVec<Self::Api, LegacyPriceLevel<Self::Api>>> {
+        match bet_type {
+            BetType::Back => self.legacy_back_levels(market_id, selection_id),
+            BetType::Lay => self.legacy_lay_levels(market_id, selection_id),
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(getStorageVersion)]
+    fn get_storage_version(&self) -> u32 {
+        self.storage_version().get()
+    }
+
+    /// Returns (storage_version, bets_left): the version a market was migrated to, zero if it was not
+    /// migrated, and the number of its bets a started migration has left to rewrite.
+    #[view(getMarketMigrationStatus)]
+    fn get_market_migration_status(&self, market_id: u64) -> MultiValue2<u32, usize> {
+        (self.market_storage_version(market_id).get(), self.migration_cursor(market_id).get()).into()
+    }
+}
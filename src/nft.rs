@@ -1,7 +1,7 @@
 use crate::{
     constants::constants::{DOMAIN_STORAGE, NFT_ROYALTIES, TOKEN_NAME, TOKEN_TICKER},
     errors::{ERR_INVALID_BET_ID, ERR_INVALID_NFT_TOKEN, ERR_INVALID_NFT_TOKEN_NONCE, ERR_INVALID_PAYMENT_COUNT, ERR_INVALID_ROLE, ERR_TOKEN_ALREADY_ISSUED, ERR_TOKEN_NOT_ISSUED},
-    types::{Bet, BetAttributes, BetStatus, BetType}
+    types::{Bet, BetAttributes, BetStatus, BetType, Role}
 };
 
 multiversx_sc::imports!();
@@ -13,18 +13,19 @@ pub type AttributesAsMultiValue<M> =
 pub trait NftModule:
     crate::storage::StorageModule
     + crate::events::EventsModule
+    + crate::roles::RolesModule
     + crate::utils::UtilsModule
 {
     //--------------------------------------------------------------------------------------------//
     //-------------------------------- Token Issuance --------------------------------------------//
     //--------------------------------------------------------------------------------------------//
 
-    /// Issues a new ESDT token for betting NFTs, callable only by the contract owner.
+    /// Issues a new ESDT token for betting NFTs (admin role).
     /// Requires an EGLD payment to cover issuance costs.
-    #[only_owner]
     #[payable("EGLD")]
     #[endpoint(issueToken)]
     fn issue_token(&self) {
+        self.require_role(Role::Admin);
         require!(self.bet_nft_token().is_empty(), ERR_TOKEN_ALREADY_ISSUED);
         let issue_cost = self.call_value().egld_value().clone_value();
         let token_name = ManagedBuffer::new_from_bytes(TOKEN_NAME);
@@ -56,11 +57,11 @@ pub trait NftModule:
         }
     }
 
-    /// Sets local roles for the NFT token, callable only by the contract owner.
+    /// Sets local roles for the NFT token (admin role).
     /// Requires the token to be issued first.
-    #[only_owner]
     #[endpoint(setLocalRoles)]
     fn set_local_roles(&self) {
+        self.require_role(Role::Admin);
         require!(!self.bet_nft_token().is_empty(), ERR_TOKEN_NOT_ISSUED);
 
         let token = self.bet_nft_token().get_token_id();
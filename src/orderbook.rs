@@ -1,11 +1,16 @@
-use crate::{errors::{ERR_INVALID_BACK_LIQUIDITY, ERR_INVALID_LAY_LIQUIDITY, ERR_INVALID_MATCHED_AMOUNT}, types::{Bet, BetStatus, BetType, MatchedPart, PriceLevel}};
+use crate::{
+    escrow,
+    types::{Bet, BetStatus, BetType, MatchedPart}
+};
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 #[multiversx_sc::module]
 pub trait OrderbookModule:
     crate::storage::StorageModule +
-    crate::events::EventsModule
+    crate::events::EventsModule +
+    crate::balance::BalanceModule +
+    crate::limits::LimitsModule
 {
     //--------------------------------------------------------------------------------------------//
     //-------------------------------- Bet Processing --------------------------------------------//
@@ -20,9 +25,8 @@ pub trait OrderbookModule:
         let mut remaining = &bet.stake_amount - &bet.total_matched;
     
         let remaining_liability = self.calculate_remaining_liability(&bet, &remaining);
-        let opposite_levels = self.get_opposite_levels(&bet);
     
-        self.match_bet_against_levels(&mut bet, &mut total_matched, &mut remaining, opposite_levels);
+        self.match_bet_against_queue(&mut bet, &mut total_matched, &mut remaining);
         self.update_bet_status_and_totals(&mut bet, &total_matched, &remaining);
     
         bet.liability = remaining_liability;
@@ -31,107 +35,62 @@ pub trait OrderbookModule:
         (bet, total_matched, remaining)
     }
     
-    // This is the match_bet_against_levels function we fixed earlier
-    fn match_bet_against_levels(
+    /// Matches a bet against the resting orders of the opposite side at the same odds, oldest first.
+    /// Only the orders actually consumed are read, so the cost does not grow with the depth of the book.
+    /// Parameters:
+    /// - bet: The bet being matched.
+    /// - total_matched: The matched amount of the bet (updated in place).
+    /// - remaining: The unmatched amount of the bet (updated in place).
+    fn match_bet_against_queue(
         &self,
         bet: &mut Bet<Self::Api>,
         total_matched: &mut BigUint<Self::Api>,
         remaining: &mut BigUint<Self::Api>,
-        mut opposite_levels: ManagedVec<Self::Api, PriceLevel<Self::Api>>,
     ) {
         let current_timestamp = self.blockchain().get_block_timestamp();
-        let mut i = 0;
-    
-        while i < opposite_levels.len() && *remaining > BigUint::zero() {
-            let mut level = opposite_levels.get(i);
-            if level.odds == bet.odd {
-                let mut updated_nonces = ManagedVec::new();
-                let mut total_level_stake = BigUint::zero();
-    
-                for bet_id in level.bet_ids.iter() {
-                    // Verifică dacă bet-ul există
-                    if self.bet_by_id(bet_id).is_empty() {
-                        continue; // Skip this bet if it doesn't exist
-                    }
-    
-                    if *remaining == BigUint::zero() {
-                        updated_nonces.push(bet_id);
-                        let matched_bet = self.bet_by_id(bet_id).get();
-                        total_level_stake += &matched_bet.stake_amount - &matched_bet.total_matched;
-                        continue;
-                    }
-    
-                    let mut matched_bet = self.bet_by_id(bet_id).get();
-                    let current_unmatched = &matched_bet.stake_amount - &matched_bet.total_matched;
-    
-                    if current_unmatched > BigUint::zero() {
-                        let to_match = core::cmp::min(current_unmatched, remaining.clone());
-                        if to_match > BigUint::zero() {
-                            *total_matched += &to_match;
-                            *remaining -= &to_match;
-                            self.add_matched_part(bet, &mut matched_bet, &to_match, current_timestamp);
-                            self.update_matched_bet(&mut matched_bet, &to_match);
-    
-                            let remaining_unmatched = &matched_bet.stake_amount - &matched_bet.total_matched;
-                            if remaining_unmatched > BigUint::zero() {
-                                updated_nonces.push(bet_id);
-                                total_level_stake += remaining_unmatched;
-                            }
-    
-                            self.bet_by_id(bet_id).set(&matched_bet);
-                        }
-                    }
-                }
-    
-                level.bet_ids = updated_nonces;
-                level.total_stake = total_level_stake;
-                self.update_opposite_levels(&mut opposite_levels, i, level);
-            }
-            i += 1;
-        }
-    
-        self.save_opposite_levels(bet, opposite_levels);
-    }
+        let opposite_side = match bet.bet_type {
+            BetType::Back => BetType::Lay,
+            BetType::Lay => BetType::Back,
+        };
+        let mut queue = self.order_queue(bet.event, bet.selection.id, opposite_side, &bet.odd);
+        let mut matched_from_level = BigUint::zero();
 
-    /// Processes matches for a specific price level and updates counterparty bets.
-    /// Parameters:
-    /// - bet: The bet being matched.
-    /// - match_amount: The amount to match at this level.
-    /// - level: The price level being processed (updated in place).
-    /// - current_timestamp: The timestamp of the match.
-    fn process_level_matches(
-        &self,
-        bet: &mut Bet<Self::Api>,
-        match_amount: &BigUint,
-        level: &mut PriceLevel<Self::Api>,
-        current_timestamp: u64,
-    ) {
-        let mut bet_ids = ManagedVec::new();
-        let mut total_level_stake = BigUint::zero();
+        while *remaining > BigUint::zero() {
+            let node = match queue.front() {
+                Some(node) => node,
+                None => break,
+            };
+            let bet_id = node.get_value_cloned();
+
+            // Verifică dacă bet-ul există
+            if self.bet_by_id(bet_id).is_empty() {
+                queue.remove_node(&node);
+                self.order_node(bet_id).clear();
+                continue;
+            }
 
-        for bet_id in level.bet_ids.iter() {
             let mut matched_bet = self.bet_by_id(bet_id).get();
             let current_unmatched = &matched_bet.stake_amount - &matched_bet.total_matched;
+            let to_match = core::cmp::min(current_unmatched.clone(), remaining.clone());
 
-            if current_unmatched > BigUint::zero() {
-                let match_this_bet = current_unmatched.min(match_amount.clone());
-                if match_this_bet > BigUint::zero() {
-                    self.add_matched_part(bet, &mut matched_bet, &match_this_bet, current_timestamp);
-                    self.update_matched_bet(&mut matched_bet, &match_this_bet);
+            *total_matched += &to_match;
+            *remaining -= &to_match;
+            matched_from_level += &to_match;
+            self.add_matched_part(bet, &mut matched_bet, &to_match, current_timestamp);
+            self.update_matched_bet(&mut matched_bet, &to_match);
+            self.bet_by_id(bet_id).set(&matched_bet);
 
-                    let remaining_unmatched = &matched_bet.stake_amount - &matched_bet.total_matched;
-                    if remaining_unmatched > BigUint::zero() {
-                        bet_ids.push(bet_id);
-                        total_level_stake += remaining_unmatched;
-                    }
-
-                    self.bet_by_id(bet_id).set(&matched_bet);
-                }
+            if to_match == current_unmatched {
+                queue.remove_node(&node);
+                self.order_node(bet_id).clear();
+                self.user_escrowed_bets(&matched_bet.bettor).swap_remove(&bet_id);
+                self.user_open_orders(&matched_bet.bettor, matched_bet.event).swap_remove(&bet_id);
             }
         }
 
-        level.bet_ids = bet_ids;
-        level.total_stake = total_level_stake;
+        if matched_from_level > BigUint::zero() {
+            self.reduce_level(bet.event, bet.selection.id, opposite_side, &bet.odd, &matched_from_level);
+        }
     }
 
     /// Updates the status of a bet and related totals after matching.
@@ -145,6 +104,7 @@ pub trait OrderbookModule:
         total_matched: &BigUint,
         remaining: &BigUint,
     ) {
+        let new_matches = total_matched - &bet.total_matched;
         bet.total_matched = total_matched.clone();
         bet.status = if *remaining == BigUint::zero() {
             bet.stake_amount = total_matched.clone();
@@ -152,19 +112,17 @@ pub trait OrderbookModule:
                 BetType::Back => total_matched.clone(),
                 BetType::Lay => bet.total_amount.clone(),
             };
-            self.selection_matched_count(bet.event, bet.selection.id).update(|val| *val += 1);
             BetStatus::Matched
         } else if *total_matched > BigUint::zero() {
-            self.selection_partially_matched_count(bet.event, bet.selection.id).update(|val| *val += 1);
             BetStatus::PartiallyMatched
         } else {
-            self.selection_unmatched_count(bet.event, bet.selection.id).update(|val| *val += 1);
             BetStatus::Unmatched
         };
+        // The bet is counted from the moment it reaches the matching engine
+        self.increment_status_count(bet.event, bet.selection.id, bet.status);
 
         bet.potential_profit = self.calculate_total_potential_profit(bet);
-        if *total_matched > BigUint::zero() {
-            let new_matches = total_matched - &bet.total_matched;
+        if new_matches > BigUint::zero() {
             self.update_total_matched(bet.event, bet.selection.id, &new_matches);
         }
 
@@ -177,92 +135,58 @@ pub trait OrderbookModule:
     //-------------------------------- Orderbook Management --------------------------------------//
     //--------------------------------------------------------------------------------------------//
 
-    /// Adds a bet to the order book if it has unmatched amounts.
+    /// Appends a bet with an unmatched amount to the back of the queue at its side and odds.
     /// Parameters:
     /// - bet: The bet to add to the order book.
     fn add_to_orderbook(&self, bet: &Bet<Self::Api>) {
         let unmatched_amount = &bet.stake_amount - &bet.total_matched;
-        if unmatched_amount == BigUint::zero() { 
+        if unmatched_amount == BigUint::zero() || !self.order_node(bet.bet_id).is_empty() {
             return;
         }
-        
-        let mut levels = match bet.bet_type {
-            BetType::Back => self.selection_back_levels(bet.event, bet.selection.id).get(),
-            BetType::Lay => self.selection_lay_levels(bet.event, bet.selection.id).get(),
-        };
-        
-        let level_index = self.find_level_index(&levels, &bet.odd);
-        match level_index {
-            Some(i) => {
-                let mut level = levels.get(i);
-                level.total_stake += &unmatched_amount;
-    
-                let mut new_nonces = ManagedVec::new();
-                let mut inserted = false;
-                for bet_id in level.bet_ids.iter() {
-                    let existing_bet = self.bet_by_id(bet_id).get();
-                    if !inserted && bet.created_at < existing_bet.created_at {
-                        new_nonces.push(bet.bet_id);
-                        inserted = true;
-                    }
-                    new_nonces.push(bet_id);
-                }
-                if !inserted {
-                    new_nonces.push(bet.bet_id);
-                }
-                level.bet_ids = new_nonces;
-    
-                let _ = levels.set(i, level);
-            },
-            None => {
-                let new_level = PriceLevel {
-                    odds: bet.odd.clone(),
-                    total_stake: unmatched_amount.clone(),
-                    bet_ids: ManagedVec::from_single_item(bet.bet_id),
-                };
-                levels.push(new_level);
-            }
-        };
-        
-        self.update_levels_and_liquidity(bet, levels, unmatched_amount.clone());
+
+        let node = self.order_queue(bet.event, bet.selection.id, bet.bet_type, &bet.odd).push_back(bet.bet_id);
+        self.order_node(bet.bet_id).set(node.get_node_id());
+        self.user_escrowed_bets(&bet.bettor).insert(bet.bet_id);
+
+        self.level_stake(bet.event, bet.selection.id, bet.bet_type, &bet.odd)
+            .update(|stake| *stake += &unmatched_amount);
+        self.price_levels(bet.event, bet.selection.id, bet.bet_type).insert(bet.odd.clone());
+        self.side_liquidity(bet.event, bet.selection.id, bet.bet_type)
+            .update(|liquidity| *liquidity += &unmatched_amount);
     }
 
-    /// Removes a bet from the order book if it has unmatched amounts.
+    /// Removes a bet from the order book in constant time, along with its escrow entry.
+    /// Bets that are not resting in the book are ignored.
     /// Parameters:
     /// - bet: The bet to remove from the order book.
     fn remove_from_orderbook(&self, bet: &Bet<Self::Api>) {
+        // Pending bets hold escrow without resting in the book
+        self.user_escrowed_bets(&bet.bettor).swap_remove(&bet.bet_id);
+        if self.order_node(bet.bet_id).is_empty() {
+            return;
+        }
+
+        let node_id = self.order_node(bet.bet_id).take();
+        self.order_queue(bet.event, bet.selection.id, bet.bet_type, &bet.odd).remove_node_by_id(node_id);
+
         let unmatched_amount = &bet.stake_amount - &bet.total_matched;
-        let mut levels = match bet.bet_type {
-            BetType::Back => self.selection_back_levels(bet.event, bet.selection.id).get(),
-            BetType::Lay => self.selection_lay_levels(bet.event, bet.selection.id).get(),
-        };
-    
-        let level_index = self.find_level_index(&levels, &bet.odd);
-        if let Some(i) = level_index {
-            let mut level = levels.get(i);
-            level.total_stake -= &unmatched_amount;  
-    
-            let mut bet_ids = ManagedVec::new();
-            for bet_id in level.bet_ids.iter() {
-                if bet_id != bet.bet_id {
-                    bet_ids.push(bet_id);
-                }
-            }
-    
-            if bet_ids.is_empty() {
-                if i < levels.len() - 1 {
-                    let last = levels.get(levels.len() - 1);
-                    let _ = levels.set(i, last);
+        self.reduce_level(bet.event, bet.selection.id, bet.bet_type, &bet.odd, &unmatched_amount);
+    }
+
+    /// Returns the IDs of every bet resting in the book of a selection, on both sides.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    fn get_resting_bet_ids(&self, market_id: u64, selection_id: u64) -> ManagedVec<Self::Api, u64> {
+        let mut bet_ids = ManagedVec::new();
+        for bet_type in [BetType::Back, BetType::Lay] {
+            for odds in self.price_levels(market_id, selection_id, bet_type).iter() {
+                for bet_id in self.order_queue(market_id, selection_id, bet_type, &odds).iter() {
+                    bet_ids.push(bet_id.into_value());
                 }
-                levels.remove(levels.len() - 1);
-            } else {
-                level.bet_ids = bet_ids;
-                let _ = levels.set(i, level);
             }
-    
-            let negative_amount = BigUint::zero() - &unmatched_amount;
-            self.update_levels_and_liquidity(bet, levels, negative_amount);
         }
+        bet_ids
     }
 
     //--------------------------------------------------------------------------------------------//
@@ -297,17 +221,6 @@ pub trait OrderbookModule:
         }
     }
 
-    /// Retrieves the opposite price levels for a bet (lay for back bets, back for lay bets).
-    /// Parameters:
-    /// - bet: The bet to get opposite levels for.
-    /// Returns: A vector of price levels.
-    fn get_opposite_levels(&self, bet: &Bet<Self::Api>) -> ManagedVec<Self::Api, PriceLevel<Self::Api>> {
-        match bet.bet_type {
-            BetType::Back => self.selection_lay_levels(bet.event, bet.selection.id).get(),
-            BetType::Lay => self.selection_back_levels(bet.event, bet.selection.id).get(),
-        }
-    }
-
     /// Adds a matched part to both the current bet and the counterparty bet.
     /// Parameters:
     /// - bet: The current bet being matched.
@@ -347,124 +260,158 @@ pub trait OrderbookModule:
     /// - matched_bet: The counterparty bet being updated.
     /// - match_amount: The amount matched.
     fn update_matched_bet(&self, matched_bet: &mut Bet<Self::Api>, match_amount: &BigUint) {
+        self.fold_legacy_escrow(matched_bet);
+        // The matched part no longer rests unmatched, so its share of the escrow leaves the locked funds.
+        // Both shares round up the same way, so the releases of a bet add up to exactly what it locked.
+        let unmatched_before = &matched_bet.stake_amount - &matched_bet.total_matched;
+        let unmatched_after = &unmatched_before - match_amount;
+        let locked_before = escrow::locked_share(matched_bet.bet_type, &matched_bet.total_amount, &matched_bet.stake_amount, &unmatched_before);
+        let locked_after = escrow::locked_share(matched_bet.bet_type, &matched_bet.total_amount, &matched_bet.stake_amount, &unmatched_after);
+        let unlocked = locked_before - locked_after;
+        self.unlock_funds(&matched_bet.bettor, &matched_bet.payment_token, &unlocked);
+        self.add_matched_escrow(&matched_bet.payment_token, &unlocked);
+
         matched_bet.total_matched += match_amount;
-        if matched_bet.bet_type == BetType::Lay {
-            let matched_ratio = match_amount / &matched_bet.stake_amount;
-            let matched_total = &matched_bet.total_amount * &matched_ratio;
-            matched_bet.total_amount -= &matched_total;
-        }
 
+        let previous_status = matched_bet.status;
         matched_bet.status = if &matched_bet.total_matched == &matched_bet.stake_amount {
             BetStatus::Matched
         } else {
             BetStatus::PartiallyMatched
         };
+        self.move_status_count(matched_bet.event, matched_bet.selection.id, previous_status, matched_bet.status);
 
         matched_bet.potential_profit = self.calculate_total_potential_profit(matched_bet);
     }
 
-    /// Updates the opposite levels after matching.
+    /// Shrinks a partially matched bet to its matched part once its unmatched remainder is refunded.
     /// Parameters:
-    /// - opposite_levels: The list of opposite levels (updated in place).
-    /// - index: The index of the level being updated.
-    /// - level: The updated price level.
-    fn update_opposite_levels(
-        &self,
-        opposite_levels: &mut ManagedVec<Self::Api, PriceLevel<Self::Api>>,
-        index: usize,
-        level: PriceLevel<Self::Api>,
-    ) {
-        if !level.bet_ids.is_empty() {
-            let _ = opposite_levels.set(index, level);
-        } else if index < opposite_levels.len() - 1 {
-            let last = opposite_levels.get(opposite_levels.len() - 1);
-            let _ = opposite_levels.set(index, last);
-            opposite_levels.remove(opposite_levels.len() - 1);
-        } else {
-            opposite_levels.remove(opposite_levels.len() - 1);
+    /// - bet: The bet being shrunk.
+    /// - retained: The escrow left for the matched part, as returned by refund_unmatched_escrow.
+    fn retain_matched_part(&self, bet: &mut Bet<Self::Api>, retained: BigUint) {
+        bet.stake_amount = bet.total_matched.clone();
+        bet.liability = match bet.bet_type {
+            BetType::Back => BigUint::zero(),
+            BetType::Lay => &retained - &bet.total_matched,
+        };
+        bet.total_amount = retained;
+        bet.status = BetStatus::Matched;
+        bet.potential_profit = self.calculate_total_potential_profit(bet);
+    }
+
+    /// Returns the best resting odds on one side of a selection's book: the highest back odds
+    /// or the lowest lay odds. Returns zero if that side is empty.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - bet_type: The side of the book.
+    fn get_best_odds(&self, market_id: u64, selection_id: u64, bet_type: BetType) -> BigUint {
+        let mut best = BigUint::zero();
+        for odds in self.price_levels(market_id, selection_id, bet_type).iter() {
+            let is_better = match bet_type {
+                BetType::Back => odds > best,
+                BetType::Lay => best == BigUint::zero() || odds < best,
+            };
+            if is_better {
+                best = odds;
+            }
         }
+        best
     }
 
-    /// Saves the updated opposite levels back to storage.
+    /// Updates the total matched amount for a selection.
     /// Parameters:
-    /// - bet: The bet being processed.
-    /// - opposite_levels: The updated list of opposite levels.
-    fn save_opposite_levels(
-        &self,
-        bet: &Bet<Self::Api>,
-        opposite_levels: ManagedVec<Self::Api, PriceLevel<Self::Api>>,
-    ) {
-        match bet.bet_type {
-            BetType::Back => self.selection_lay_levels(bet.event, bet.selection.id).set(&opposite_levels),
-            BetType::Lay => self.selection_back_levels(bet.event, bet.selection.id).set(&opposite_levels),
-        };
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - matched_amount: The amount to add to the total matched.
+    fn update_total_matched(&self, market_id: u64, selection_id: u64, matched_amount: &BigUint) {
+        self.total_matched_amount(market_id, selection_id)
+            .update(|total| *total += matched_amount);
     }
 
-    /// Finds the index of a level with matching odds in the order book.
+    /// Takes an amount out of a price level and of the side's liquidity, dropping the level once its queue is empty.
     /// Parameters:
-    /// - levels: The list of price levels.
-    /// - odds: The odds to match.
-    /// Returns: The index of the matching level, if found.
-    fn find_level_index(
-        &self,
-        levels: &ManagedVec<Self::Api, PriceLevel<Self::Api>>,
-        odds: &BigUint,
-    ) -> Option<usize> {
-        for i in 0..levels.len() {
-            let level = levels.get(i);
-            if level.odds == *odds {
-                return Some(i);
-            }
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - bet_type: The side of the book.
+    /// - odds: The odds of the level.
+    /// - amount: The stake leaving the level.
+    fn reduce_level(&self, market_id: u64, selection_id: u64, bet_type: BetType, odds: &BigUint, amount: &BigUint) {
+        self.side_liquidity(market_id, selection_id, bet_type)
+            .update(|liquidity| *liquidity -= amount);
+
+        if self.order_queue(market_id, selection_id, bet_type, odds).is_empty() {
+            self.level_stake(market_id, selection_id, bet_type, odds).clear();
+            self.price_levels(market_id, selection_id, bet_type).swap_remove(odds);
+        } else {
+            self.level_stake(market_id, selection_id, bet_type, odds)
+                .update(|stake| *stake -= amount);
+        }
+    }
+
+    fn side_liquidity(&self, market_id: u64, selection_id: u64, bet_type: BetType) -> SingleValueMapper<BigUint> {
+        match bet_type {
+            BetType::Back => self.selection_back_liquidity(market_id, selection_id),
+            BetType::Lay => self.selection_lay_liquidity(market_id, selection_id),
         }
-        None
     }
 
-    /// Updates levels and liquidity in storage after adding or removing a bet.
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Selection Counters ----------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Returns the counter of a selection that holds the bets in a given status, if that status is counted.
+    /// Pending bets are not counted until they reach the matching engine, and claimed bets stay counted as wins.
     /// Parameters:
-    /// - bet: The bet affecting the levels.
-    /// - levels: The updated list of price levels.
-    /// - amount_change: The change in liquidity (positive for add, negative for remove).
-    fn update_levels_and_liquidity(
-        &self,
-        bet: &Bet<Self::Api>,
-        levels: ManagedVec<Self::Api, PriceLevel<Self::Api>>,
-        amount_change: BigUint
-    ) {
-        match bet.bet_type {
-            BetType::Back => {
-                self.selection_back_levels(bet.event, bet.selection.id).set(&levels);
-                self.selection_back_liquidity(bet.event, bet.selection.id)
-                    .update(|val| *val += &amount_change);
-                require!(
-                    self.selection_back_liquidity(bet.event, bet.selection.id).get() >= BigUint::zero(),
-                    ERR_INVALID_BACK_LIQUIDITY
-                );
-            },
-            BetType::Lay => {
-                self.selection_lay_levels(bet.event, bet.selection.id).set(&levels);
-                self.selection_lay_liquidity(bet.event, bet.selection.id)
-                    .update(|val| *val += &amount_change);
-                require!(
-                    self.selection_lay_liquidity(bet.event, bet.selection.id).get() >= BigUint::zero(),
-                    ERR_INVALID_LAY_LIQUIDITY
-                );
-            },
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - status: The bet status.
+    fn status_count(&self, market_id: u64, selection_id: u64, status: BetStatus) -> Option<SingleValueMapper<u64>> {
+        match status {
+            BetStatus::Unmatched => Some(self.selection_unmatched_count(market_id, selection_id)),
+            BetStatus::PartiallyMatched => Some(self.selection_partially_matched_count(market_id, selection_id)),
+            BetStatus::Matched => Some(self.selection_matched_count(market_id, selection_id)),
+            BetStatus::Win | BetStatus::Claimed => Some(self.selection_win_count(market_id, selection_id)),
+            BetStatus::Lost => Some(self.selection_lost_count(market_id, selection_id)),
+            BetStatus::Void => Some(self.selection_void_count(market_id, selection_id)),
+            BetStatus::Pending => None,
         }
     }
 
-    /// Updates the total matched amount for a selection.
+    fn increment_status_count(&self, market_id: u64, selection_id: u64, status: BetStatus) {
+        if let Some(count) = self.status_count(market_id, selection_id, status) {
+            count.update(|val| *val += 1);
+        }
+    }
+
+    /// Saturates at zero, since counters of markets created before they were kept in sync may lag behind.
+    fn decrement_status_count(&self, market_id: u64, selection_id: u64, status: BetStatus) {
+        if let Some(count) = self.status_count(market_id, selection_id, status) {
+            count.update(|val| *val = val.saturating_sub(1));
+        }
+    }
+
+    /// Moves a bet from the counter of its previous status to the counter of its new status.
     /// Parameters:
     /// - market_id: The ID of the market.
     /// - selection_id: The ID of the selection.
-    /// - matched_amount: The amount to add to the total matched.
-    fn update_total_matched(&self, market_id: u64, selection_id: u64, matched_amount: &BigUint) {
-        self.total_matched_amount(market_id, selection_id)
-            .update(|total| {
-                *total += matched_amount;
-                require!(
-                    *total <= self.selection_back_liquidity(market_id, selection_id).get(),
-                    ERR_INVALID_MATCHED_AMOUNT
-             );
-        });
+    /// - from: The previous status of the bet.
+    /// - to: The new status of the bet.
+    fn move_status_count(&self, market_id: u64, selection_id: u64, from: BetStatus, to: BetStatus) {
+        if from == to {
+            return;
+        }
+        self.decrement_status_count(market_id, selection_id, from);
+        self.increment_status_count(market_id, selection_id, to);
+    }
+
+    /// Counts a bet that leaves the book without any matched part, either cancelled or refunded.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - from: The status of the bet before it was cancelled.
+    fn record_canceled_bet(&self, market_id: u64, selection_id: u64, from: BetStatus) {
+        self.decrement_status_count(market_id, selection_id, from);
+        self.selection_canceled_count(market_id, selection_id).update(|val| *val += 1);
     }
-}
\ No newline at end of file
+}
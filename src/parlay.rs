@@ -0,0 +1,333 @@
+use crate::{
+    constants::constants::{MAX_PARLAY_LEGS, ODDS_PRECISION},
+    errors::{ERR_INSUFFICIENT_HOUSE_LIQUIDITY, ERR_INVALID_DEPOSIT_TOKEN, ERR_INVALID_MARKET, ERR_NO_ESCROW_DUST, ERR_NOTHING_TO_DEPOSIT, ERR_ODDS_TOO_LOW, ERR_PAUSED, ERR_PARLAY_LEGS_PENDING, ERR_PARLAY_LEG_IN_PLAY, ERR_PARLAY_LEG_NOT_OFFERED, ERR_PARLAY_NOT_FOUND, ERR_PARLAY_NOT_OPEN, ERR_PARLAY_ODDS_BELOW_MIN, ERR_PARLAY_SAME_EVENT, ERR_PARLAY_TOO_FEW_LEGS, ERR_PARLAY_TOO_MANY_LEGS},
+    types::{Parlay, ParlayLeg, ParlayStatus, Role}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait ParlayModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::balance::BalanceModule
+    + crate::limits::LimitsModule
+    + crate::pause::PauseModule
+    + crate::validation::ValidationModule
+    + crate::utils::UtilsModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- House Liquidity -------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Adds the attached payment to the house pool backing parlays (treasury role).
+    /// Parlays cannot be matched peer-to-peer, so the house takes the other side and keeps the full
+    /// potential payout of every open parlay reserved until it is settled.
+    #[payable("*")]
+    #[endpoint(fundHouse)]
+    fn fund_house(&self) {
+        self.require_role(Role::Treasury);
+        let (token, nonce, amount) = self
+            .call_value()
+            .egld_or_single_esdt()
+            .into_tuple();
+        require!(nonce == 0, ERR_INVALID_DEPOSIT_TOKEN);
+        require!(amount > BigUint::zero(), ERR_NOTHING_TO_DEPOSIT);
+
+        self.house_pool(&token).update(|pool| *pool += &amount);
+        self.house_funded_event(&token, &amount);
+    }
+
+    /// Withdraws unreserved liquidity from the house pool to the caller (treasury role).
+    /// Parameters:
+    /// - token: The token to withdraw.
+    /// - amount: The amount to withdraw.
+    #[endpoint(withdrawHouse)]
+    fn withdraw_house(&self, token: EgldOrEsdtTokenIdentifier, amount: BigUint) {
+        self.require_role(Role::Treasury);
+        self.require_not_timelocked();
+        self.apply_house_withdrawal(&token, &amount, &self.blockchain().get_caller());
+    }
+
+    /// Moves the rounding dust kept from refunded lay escrow into the house pool (treasury role).
+    /// The funds stay in the contract, so withdrawing them still goes through withdrawHouse.
+    /// Parameters:
+    /// - token: The token whose dust is swept.
+    #[endpoint(sweepEscrowDust)]
+    fn sweep_escrow_dust(&self, token: EgldOrEsdtTokenIdentifier) {
+        self.require_role(Role::Treasury);
+        let dust = self.escrow_dust(&token).take();
+        require!(dust > BigUint::zero(), ERR_NO_ESCROW_DUST);
+
+        self.house_pool(&token).update(|pool| *pool += &dust);
+        self.escrow_dust_swept_event(&token, &dust);
+    }
+
+    /// Sets the odds the house offers for a selection when used as a parlay leg (risk manager role).
+    /// Setting zero odds withdraws the selection from parlays.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - odds: The offered odds (e.g., 250 for 2.50).
+    #[endpoint(setParlayLegOdds)]
+    fn set_parlay_leg_odds(&self, market_id: u64, selection_id: u64, odds: BigUint) {
+        self.require_role(Role::RiskManager);
+        self.require_not_timelocked();
+        self.apply_parlay_leg_odds(market_id, selection_id, &odds);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Parlay Placement ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Places a parlay on selections from different events, at the odds currently offered by the house.
+    /// Every leg must be on a market that has not reached its close time.
+    /// Parameters:
+    /// - min_combined_odds: The lowest combined odds the bettor accepts, protecting against odds changes.
+    /// - legs: List of (market_id, selection_id).
+    /// Returns: The ID of the new parlay.
+    #[payable("*")]
+    #[endpoint(placeParlay)]
+    fn place_parlay(
+        &self,
+        min_combined_odds: BigUint,
+        legs: MultiValueEncoded<MultiValue2<u64, u64>>,
+    ) -> u64 {
+        let caller = self.blockchain().get_caller();
+        let (token_identifier, token_nonce, stake_amount) = self
+            .call_value()
+            .egld_or_single_esdt()
+            .into_tuple();
+        require!(token_nonce == 0, ERR_INVALID_DEPOSIT_TOKEN);
+        self.validate_bet_amount(&stake_amount);
+        self.enforce_deposit_limits(&caller, &token_identifier, &stake_amount);
+        self.enforce_bet_limits(&caller, &token_identifier, &stake_amount, &stake_amount);
+
+        let legs_count = legs.len();
+        require!(legs_count >= 2, ERR_PARLAY_TOO_FEW_LEGS);
+        require!(legs_count <= MAX_PARLAY_LEGS, ERR_PARLAY_TOO_MANY_LEGS);
+
+        let mut parlay_legs: ManagedVec<ParlayLeg<Self::Api>> = ManagedVec::new();
+        let mut combined_odds = BigUint::from(ODDS_PRECISION);
+
+        for leg in legs {
+            let (market_id, selection_id) = leg.into_tuple();
+            self.validate_market(market_id);
+            self.require_not_paused(market_id);
+            self.validate_selection(market_id, selection_id);
+            // In-play markets would let a leg skip the bet delay single bets wait behind
+            require!(
+                self.blockchain().get_block_timestamp() < self.markets(market_id).get().close_timestamp,
+                ERR_PARLAY_LEG_IN_PLAY
+            );
+
+            require!(!self.is_same_event_leg(&parlay_legs, market_id), ERR_PARLAY_SAME_EVENT);
+
+            let odds = self.parlay_leg_odds(market_id, selection_id).get();
+            require!(odds > BigUint::zero(), ERR_PARLAY_LEG_NOT_OFFERED);
+
+            combined_odds = &combined_odds * &odds / &BigUint::from(ODDS_PRECISION);
+            parlay_legs.push(ParlayLeg {
+                market_id,
+                selection_id,
+                odds,
+            });
+        }
+        require!(combined_odds >= min_combined_odds, ERR_PARLAY_ODDS_BELOW_MIN);
+
+        let potential_payout = &stake_amount * &combined_odds / &BigUint::from(ODDS_PRECISION);
+        let house_liability = &potential_payout - &stake_amount;
+        require!(
+            self.house_pool(&token_identifier).get() >= house_liability,
+            ERR_INSUFFICIENT_HOUSE_LIQUIDITY
+        );
+
+        self.house_pool(&token_identifier).update(|pool| *pool -= &house_liability);
+        self.house_reserved(&token_identifier).update(|reserved| *reserved += &potential_payout);
+
+        let parlay_id = self.next_parlay_id().get() + 1;
+        self.next_parlay_id().set(parlay_id);
+
+        let parlay = Parlay {
+            parlay_id,
+            bettor: caller.clone(),
+            legs: parlay_legs,
+            stake_amount: stake_amount.clone(),
+            combined_odds: combined_odds.clone(),
+            potential_payout: potential_payout.clone(),
+            payment_token: token_identifier,
+            status: ParlayStatus::Open,
+            created_at: self.blockchain().get_block_timestamp(),
+        };
+        self.parlays(parlay_id).set(&parlay);
+        self.user_parlays(&caller).insert(parlay_id);
+
+        self.parlay_placed_event(
+            &caller,
+            parlay_id,
+            legs_count,
+            &stake_amount,
+            &combined_odds,
+            &potential_payout
+        );
+
+        parlay_id
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Parlay Settlement -----------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Settles a parlay once its outcome is known, callable by anyone.
+    /// A parlay is lost as soon as one leg loses; otherwise every leg must be resulted or voided.
+    /// Legs whose market has a pending result correction count as unresulted.
+    /// Void legs count at odds of 1.00 and the winnings are credited to the bettor's balance.
+    /// Parameters:
+    /// - parlay_id: The ID of the parlay to settle.
+    #[endpoint(settleParlay)]
+    fn settle_parlay(&self, parlay_id: u64) {
+        require!(!self.parlays(parlay_id).is_empty(), ERR_PARLAY_NOT_FOUND);
+        let mut parlay = self.parlays(parlay_id).get();
+        require!(parlay.status == ParlayStatus::Open, ERR_PARLAY_NOT_OPEN);
+        require!(!self.global_paused().get(), ERR_PAUSED);
+
+        let mut effective_odds = BigUint::from(ODDS_PRECISION);
+        let mut has_pending_leg = false;
+        let mut has_lost_leg = false;
+        let mut all_legs_void = true;
+
+        for leg in parlay.legs.iter() {
+            if self.market_void(leg.market_id).get() {
+                continue;
+            }
+            all_legs_void = false;
+
+            if self.winning_selection(leg.market_id).is_empty()
+                || self.pending_result_corrections(leg.market_id).get() > 0
+            {
+                has_pending_leg = true;
+                continue;
+            }
+
+            if self.winning_selection(leg.market_id).get() != leg.selection_id {
+                has_lost_leg = true;
+                break;
+            }
+
+            effective_odds = &effective_odds * &leg.odds / &BigUint::from(ODDS_PRECISION);
+        }
+
+        require!(has_lost_leg || !has_pending_leg, ERR_PARLAY_LEGS_PENDING);
+
+        let payout = if has_lost_leg {
+            parlay.status = ParlayStatus::Lost;
+            BigUint::zero()
+        } else {
+            parlay.status = if all_legs_void {
+                ParlayStatus::Void
+            } else {
+                ParlayStatus::Won
+            };
+            &parlay.stake_amount * &effective_odds / &BigUint::from(ODDS_PRECISION)
+        };
+
+        let token = &parlay.payment_token;
+        self.release_max_loss(&parlay.bettor, token, &parlay.stake_amount);
+        if has_lost_leg {
+            self.record_bet_result(&parlay.bettor, token, &parlay.stake_amount, &BigUint::zero());
+        } else if payout > parlay.stake_amount {
+            self.record_bet_result(&parlay.bettor, token, &BigUint::zero(), &(&payout - &parlay.stake_amount));
+        }
+        self.house_reserved(token).update(|reserved| *reserved -= &parlay.potential_payout);
+        self.house_pool(token).update(|pool| *pool += &(&parlay.potential_payout - &payout));
+        self.credit_balance(&parlay.bettor, token, &payout);
+
+        self.parlays(parlay_id).set(&parlay);
+
+        self.parlay_settled_event(
+            &parlay.bettor,
+            parlay_id,
+            parlay.status as u8,
+            &payout
+        );
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Sends unreserved liquidity from the house pool, directly or through an executed proposal.
+    /// Parameters:
+    /// - token: The token to withdraw.
+    /// - amount: The amount to withdraw.
+    /// - recipient: The address receiving the funds.
+    fn apply_house_withdrawal(&self, token: &EgldOrEsdtTokenIdentifier, amount: &BigUint, recipient: &ManagedAddress) {
+        require!(self.house_pool(token).get() >= *amount, ERR_INSUFFICIENT_HOUSE_LIQUIDITY);
+
+        self.house_pool(token).update(|pool| *pool -= amount);
+        self.send().direct(recipient, token, 0, amount);
+        self.house_withdrawn_event(token, amount);
+    }
+
+    /// Sets or clears the parlay leg odds of a selection, directly or through an executed proposal.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - odds: The offered odds, zero withdrawing the selection from parlays.
+    fn apply_parlay_leg_odds(&self, market_id: u64, selection_id: u64, odds: &BigUint) {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        self.validate_selection(market_id, selection_id);
+
+        if *odds == BigUint::zero() {
+            self.parlay_leg_odds(market_id, selection_id).clear();
+        } else {
+            require!(*odds > ODDS_PRECISION, ERR_ODDS_TOO_LOW);
+            self.parlay_leg_odds(market_id, selection_id).set(odds);
+        }
+    }
+
+    /// Returns true if one of the legs is already on the event of the given market.
+    /// Parameters:
+    /// - legs: The legs collected so far.
+    /// - market_id: The market of the new leg.
+    fn is_same_event_leg(&self, legs: &ManagedVec<ParlayLeg<Self::Api>>, market_id: u64) -> bool {
+        let event_id = self.markets(market_id).get().event_id;
+        let sport = self.find_market_sport(market_id);
+
+        legs.iter().any(|leg| {
+            self.markets(leg.market_id).get().event_id == event_id
+                && self.find_market_sport(leg.market_id) == sport
+        })
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(getParlay)]
+    fn get_parlay(&self, parlay_id: u64) -> Parlay<Self::Api> {
+        require!(!self.parlays(parlay_id).is_empty(), ERR_PARLAY_NOT_FOUND);
+        self.parlays(parlay_id).get()
+    }
+
+    #[view(getUserParlays)]
+    fn get_user_parlays(&self, address: ManagedAddress) -> MultiValueEncoded<u64> {
+        self.user_parlays(&address).iter().collect()
+    }
+
+    #[view(getParlayLegOdds)]
+    fn get_parlay_leg_odds(&self, market_id: u64, selection_id: u64) -> BigUint {
+        self.parlay_leg_odds(market_id, selection_id).get()
+    }
+
+    #[view(getHousePool)]
+    fn get_house_pool(&self, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.house_pool(&token).get()
+    }
+
+    #[view(getHouseReserved)]
+    fn get_house_reserved(&self, token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.house_reserved(&token).get()
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    constants::constants::BPS_DENOMINATOR,
+    errors::{ERR_MARKET_PAUSED, ERR_NOT_GUARDIAN, ERR_PAUSED, ERR_SPORT_PAUSED},
+    types::{sport_index, BreakerWindow, CircuitBreakerConfig, MarketStatus, Role, SportId}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait PauseModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::utils::UtilsModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Pause Controls --------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Sets the guardian, an address allowed to pause but not to unpause (admin role).
+    /// Parameters:
+    /// - guardian: The guardian address.
+    #[endpoint(setGuardian)]
+    fn set_guardian(&self, guardian: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        self.guardian().set(&guardian);
+    }
+
+    /// Pauses or unpauses betting, cancellations, claims and settlement on every market.
+    /// The guardian can only pause; unpausing requires the risk manager role.
+    /// Withdrawals and refunds of expired markets stay available while paused.
+    /// Parameters:
+    /// - paused: Whether to pause.
+    #[endpoint(setGlobalPause)]
+    fn set_global_pause(&self, paused: bool) {
+        let caller = self.require_pause_permission(paused);
+        self.global_paused().set(paused);
+        self.global_pause_changed_event(paused, &caller);
+    }
+
+    /// Pauses or unpauses a single market (risk manager or guardian, unpausing only by the risk manager).
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - paused: Whether to pause.
+    #[endpoint(setMarketPause)]
+    fn set_market_pause(&self, market_id: u64, paused: bool) {
+        let caller = self.require_pause_permission(paused);
+        self.market_paused(market_id).set(paused);
+        self.market_pause_changed_event(market_id, paused, &caller);
+    }
+
+    /// Pauses or unpauses every market of a sport (risk manager or guardian, unpausing only by the risk manager).
+    /// Parameters:
+    /// - sport: The sport.
+    /// - paused: Whether to pause.
+    #[endpoint(setSportPause)]
+    fn set_sport_pause(&self, sport: SportId, paused: bool) {
+        let caller = self.require_pause_permission(paused);
+        self.sport_paused(sport).set(paused);
+        self.sport_pause_changed_event(sport_index(sport), paused, &caller);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Circuit Breaker -------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Configures the circuit breaker that suspends a market when one of its selections trades too much
+    /// or its matched odds move too far within a short window (risk manager role). Zero thresholds are not checked.
+    /// Parameters:
+    /// - window_seconds: The length of the observation window.
+    /// - max_matched_volume: The maximum amount matched on a selection within the window.
+    /// - max_price_move_bps: The maximum move of matched odds from the first match in the window, in basis points.
+    #[endpoint(setCircuitBreaker)]
+    fn set_circuit_breaker(&self, window_seconds: u64, max_matched_volume: BigUint, max_price_move_bps: u64) {
+        self.require_role(Role::RiskManager);
+        self.require_not_timelocked();
+        self.circuit_breaker_config().set(&CircuitBreakerConfig {
+            window_seconds,
+            max_matched_volume,
+            max_price_move_bps,
+        });
+    }
+
+    /// Records a match in the selection's breaker window and suspends the market if a threshold is crossed.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - odds: The odds the amount was matched at.
+    /// - matched_amount: The amount matched.
+    fn check_circuit_breaker(&self, market_id: u64, selection_id: u64, odds: &BigUint, matched_amount: &BigUint) {
+        if self.circuit_breaker_config().is_empty() || *matched_amount == BigUint::zero() {
+            return;
+        }
+
+        let config = self.circuit_breaker_config().get();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let window_mapper = self.breaker_window(market_id, selection_id);
+
+        let mut window = if window_mapper.is_empty() {
+            self.new_breaker_window(current_timestamp, odds)
+        } else {
+            let window = window_mapper.get();
+            if current_timestamp >= window.window_start + config.window_seconds {
+                self.new_breaker_window(current_timestamp, odds)
+            } else {
+                window
+            }
+        };
+        window.matched_volume += matched_amount;
+
+        let volume_tripped = config.max_matched_volume > BigUint::zero()
+            && window.matched_volume > config.max_matched_volume;
+
+        let price_move = if *odds > window.start_odds {
+            odds - &window.start_odds
+        } else {
+            &window.start_odds - odds
+        };
+        let price_tripped = config.max_price_move_bps > 0
+            && price_move * BigUint::from(BPS_DENOMINATOR) > &window.start_odds * &BigUint::from(config.max_price_move_bps);
+
+        if !volume_tripped && !price_tripped {
+            window_mapper.set(&window);
+            return;
+        }
+
+        window_mapper.clear();
+
+        let mut market = self.markets(market_id).get();
+        if market.market_status == MarketStatus::Open {
+            market.market_status = MarketStatus::Suspended;
+            self.markets(market_id).set(&market);
+            self.market_suspended_event(market_id, false, current_timestamp);
+        }
+        self.circuit_breaker_tripped_event(market_id, selection_id, &window.matched_volume, odds, current_timestamp);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Fails if trading on the market is paused globally, for its sport or for the market itself.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn require_not_paused(&self, market_id: u64) {
+        require!(!self.global_paused().get(), ERR_PAUSED);
+        require!(!self.market_paused(market_id).get(), ERR_MARKET_PAUSED);
+        if let Some(sport) = self.find_market_sport(market_id) {
+            require!(!self.sport_paused(sport).get(), ERR_SPORT_PAUSED);
+        }
+    }
+
+    /// Fails unless the caller may apply the pause change and returns the caller.
+    /// Parameters:
+    /// - paused: Whether the change pauses (guardian allowed) or unpauses (risk manager only).
+    fn require_pause_permission(&self, paused: bool) -> ManagedAddress {
+        let caller = self.blockchain().get_caller();
+        let is_risk_manager = self.has_role_internal(Role::RiskManager, &caller);
+        let is_guardian = !self.guardian().is_empty() && self.guardian().get() == caller;
+        require!(is_risk_manager || (paused && is_guardian), ERR_NOT_GUARDIAN);
+        caller
+    }
+
+    fn new_breaker_window(&self, timestamp: u64, odds: &BigUint) -> BreakerWindow<Self::Api> {
+        BreakerWindow {
+            window_start: timestamp,
+            matched_volume: BigUint::zero(),
+            start_odds: odds.clone(),
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(isGloballyPaused)]
+    fn is_globally_paused(&self) -> bool {
+        self.global_paused().get()
+    }
+
+    #[view(isMarketPaused)]
+    fn is_market_paused(&self, market_id: u64) -> bool {
+        self.market_paused(market_id).get()
+    }
+
+    #[view(isSportPaused)]
+    fn is_sport_paused(&self, sport: SportId) -> bool {
+        self.sport_paused(sport).get()
+    }
+
+    #[view(getGuardian)]
+    fn get_guardian(&self) -> OptionalValue<ManagedAddress> {
+        if self.guardian().is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.guardian().get())
+        }
+    }
+}
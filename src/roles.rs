@@ -0,0 +1,121 @@
+use crate::{
+    errors::{ERR_ADMINS_BELOW_THRESHOLD, ERR_MISSING_ROLE, ERR_ROLE_ALREADY_GRANTED, ERR_ROLE_NOT_GRANTED, ERR_TIMELOCKED_CHANGE},
+    types::Role
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait RolesModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Role Management -------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Grants a role to an address (admin only).
+    /// Parameters:
+    /// - role: The role to grant.
+    /// - address: The address receiving the role.
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, address: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        require!(self.role_members(role).insert(address.clone()), ERR_ROLE_ALREADY_GRANTED);
+
+        self.role_granted_event(role, &address, &self.blockchain().get_caller());
+    }
+
+    /// Revokes a role from an address (admin only).
+    /// An admin cannot be revoked if fewer admins than the required approvals would remain.
+    /// Parameters:
+    /// - role: The role to revoke.
+    /// - address: The address losing the role.
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, address: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        self.remove_role_member(role, &address);
+
+        self.role_revoked_event(role, &address, &self.blockchain().get_caller());
+    }
+
+    /// Gives up a role held by the caller, e.g. when rotating a compromised key.
+    /// An admin cannot renounce if fewer admins than the required approvals would remain.
+    /// Parameters:
+    /// - role: The role to renounce.
+    #[endpoint(renounceRole)]
+    fn renounce_role(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        self.remove_role_member(role, &caller);
+
+        self.role_revoked_event(role, &caller, &caller);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Fails unless the caller holds the role.
+    /// Parameters:
+    /// - role: The required role.
+    fn require_role(&self, role: Role) {
+        let caller = self.blockchain().get_caller();
+        require!(self.has_role_internal(role, &caller), ERR_MISSING_ROLE);
+    }
+
+    /// Fails once governance is enabled, since the change must then go through a timelocked proposal.
+    fn require_not_timelocked(&self) {
+        require!(self.required_approvals().get() == 0, ERR_TIMELOCKED_CHANGE);
+    }
+
+    /// Removes the role from an address. Removing an admin fails if fewer admins than the required
+    /// approvals would remain, since no proposal could ever be executed again.
+    /// Parameters:
+    /// - role: The role to remove.
+    /// - address: The address losing the role.
+    fn remove_role_member(&self, role: Role, address: &ManagedAddress) {
+        require!(self.role_members(role).swap_remove(address), ERR_ROLE_NOT_GRANTED);
+        if role == Role::Admin {
+            require!(
+                self.role_members(Role::Admin).len() as u64 >= self.required_approvals().get(),
+                ERR_ADMINS_BELOW_THRESHOLD
+            );
+        }
+    }
+
+    /// Returns true if the address holds the role.
+    /// Parameters:
+    /// - role: The role to check.
+    /// - address: The address to check.
+    fn has_role_internal(&self, role: Role, address: &ManagedAddress) -> bool {
+        self.role_members(role).contains(address)
+    }
+
+    /// Makes the owner the first admin while nobody holds the admin role, so that roles can be
+    /// handed out after deployment. The owner holds no other role implicitly.
+    fn seed_initial_admin(&self) {
+        if !self.role_members(Role::Admin).is_empty() {
+            return;
+        }
+
+        let owner = self.blockchain().get_owner_address();
+        self.role_members(Role::Admin).insert(owner.clone());
+        self.role_granted_event(Role::Admin, &owner, &owner);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: ManagedAddress) -> bool {
+        self.has_role_internal(role, &address)
+    }
+
+    #[view(getRoleMembers)]
+    fn get_role_members(&self, role: Role) -> MultiValueEncoded<ManagedAddress> {
+        self.role_members(role).iter().collect()
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{
+    constants::constants::{NO_LINE, SPORT_BASKETBALL, SPORT_COUNTER_STRIKE, SPORT_DOTA, SPORT_FOOTBALL, SPORT_LEAGUE_OF_LEGENDS, TOTAL_GOALS_LINE},
+    errors::{ERR_INVALID_SCORE, ERR_INVALID_SPORT_ID, ERR_NO_MARKET_TEMPLATES, ERR_SPORT_ALREADY_EXISTS, ERR_SPORT_DISABLED, ERR_SPORT_NOT_FOUND, ERR_TEMPLATE_NEEDS_DRAW},
+    types::{MarketTemplate, MarketType, ResultFormat, Role, SportConfig, SportId}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait SportModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Sport Registry --------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Registers a new sport (admin role). It starts enabled.
+    /// Parameters:
+    /// - sport_id: The ID of the sport, unique in the registry and below 255.
+    /// - name: The display name of the sport.
+    /// - result_format: How event results are reported (draws allowed or not).
+    /// - templates: List of (market_type, line) created by createMarkets for every event of the sport.
+    #[endpoint(registerSport)]
+    fn register_sport(
+        &self,
+        sport_id: SportId,
+        name: ManagedBuffer,
+        result_format: ResultFormat,
+        templates: MultiValueEncoded<MultiValue2<MarketType, u64>>,
+    ) {
+        self.require_role(Role::Admin);
+        require!(sport_id < SportId::MAX, ERR_INVALID_SPORT_ID);
+        require!(self.sports(sport_id).is_empty(), ERR_SPORT_ALREADY_EXISTS);
+
+        let mut market_templates = ManagedVec::new();
+        for template in templates {
+            let (market_type, line) = template.into_tuple();
+            market_templates.push(MarketTemplate { market_type, line });
+        }
+        self.store_sport(sport_id, name, result_format, market_templates);
+    }
+
+    /// Replaces the market templates of a sport (admin role). Existing markets are not affected.
+    /// Parameters:
+    /// - sport_id: The ID of the sport.
+    /// - templates: List of (market_type, line).
+    #[endpoint(setSportTemplates)]
+    fn set_sport_templates(&self, sport_id: SportId, templates: MultiValueEncoded<MultiValue2<MarketType, u64>>) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+
+        let mut market_templates = ManagedVec::new();
+        for template in templates {
+            let (market_type, line) = template.into_tuple();
+            market_templates.push(MarketTemplate { market_type, line });
+        }
+        self.apply_sport_templates(sport_id, market_templates);
+    }
+
+    /// Enables or disables a sport (admin role). Disabled sports accept no new events or markets;
+    /// existing markets keep trading and settling.
+    /// Parameters:
+    /// - sport_id: The ID of the sport.
+    /// - enabled: Whether the sport is enabled.
+    #[endpoint(setSportEnabled)]
+    fn set_sport_enabled(&self, sport_id: SportId, enabled: bool) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        self.apply_sport_enabled(sport_id, enabled);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Replaces the market templates of a sport, directly or through an executed proposal.
+    /// Parameters:
+    /// - sport_id: The ID of the sport.
+    /// - market_templates: The new templates.
+    fn apply_sport_templates(&self, sport_id: SportId, market_templates: ManagedVec<MarketTemplate>) {
+        let mut sport = self.require_sport(sport_id);
+        self.validate_market_templates(&market_templates, sport.result_format);
+
+        sport.market_templates = market_templates;
+        self.sports(sport_id).set(&sport);
+    }
+
+    /// Enables or disables a sport, directly or through an executed proposal.
+    /// Parameters:
+    /// - sport_id: The ID of the sport.
+    /// - enabled: Whether the sport is enabled.
+    fn apply_sport_enabled(&self, sport_id: SportId, enabled: bool) {
+        let mut sport = self.require_sport(sport_id);
+
+        sport.enabled = enabled;
+        self.sports(sport_id).set(&sport);
+        self.sport_enabled_changed_event(sport_id, enabled);
+    }
+
+    /// Registers the sports that existed before the registry, keeping their IDs and market sets.
+    /// Sports already registered are left untouched, so this is safe to run on every upgrade.
+    fn register_default_sports(&self) {
+        let mut football_templates = ManagedVec::new();
+        football_templates.push(MarketTemplate { market_type: MarketType::FullTimeResult, line: NO_LINE });
+        football_templates.push(MarketTemplate { market_type: MarketType::TotalGoals, line: TOTAL_GOALS_LINE });
+        football_templates.push(MarketTemplate { market_type: MarketType::BothTeamsToScore, line: NO_LINE });
+
+        let mut winner_templates = ManagedVec::new();
+        winner_templates.push(MarketTemplate { market_type: MarketType::Winner, line: NO_LINE });
+
+        let defaults: [(SportId, &[u8]); 4] = [
+            (SPORT_BASKETBALL, b"Basketball"),
+            (SPORT_COUNTER_STRIKE, b"Counter-Strike"),
+            (SPORT_DOTA, b"Dota"),
+            (SPORT_LEAGUE_OF_LEGENDS, b"League of Legends"),
+        ];
+
+        if self.sports(SPORT_FOOTBALL).is_empty() {
+            self.store_sport(SPORT_FOOTBALL, ManagedBuffer::from(b"Football"), ResultFormat::Score, football_templates);
+        }
+        for (sport_id, name) in defaults.iter() {
+            if self.sports(*sport_id).is_empty() {
+                self.store_sport(*sport_id, ManagedBuffer::from(*name), ResultFormat::ScoreNoDraw, winner_templates.clone());
+            }
+        }
+    }
+
+    fn store_sport(
+        &self,
+        sport_id: SportId,
+        name: ManagedBuffer,
+        result_format: ResultFormat,
+        market_templates: ManagedVec<MarketTemplate>,
+    ) {
+        self.validate_market_templates(&market_templates, result_format);
+
+        let sport = SportConfig {
+            sport_id,
+            name,
+            market_templates,
+            result_format,
+            enabled: true,
+        };
+        self.sports(sport_id).set(&sport);
+        self.sport_ids().insert(sport_id);
+        self.sport_registered_event(sport_id, &sport.name);
+    }
+
+    /// Fails if there are no templates or if a template cannot be resulted with the result format.
+    /// Parameters:
+    /// - market_templates: The templates to check.
+    /// - result_format: The result format of the sport.
+    fn validate_market_templates(&self, market_templates: &ManagedVec<MarketTemplate>, result_format: ResultFormat) {
+        require!(!market_templates.is_empty(), ERR_NO_MARKET_TEMPLATES);
+        for template in market_templates.iter() {
+            require!(
+                template.market_type != MarketType::FullTimeResult || result_format == ResultFormat::Score,
+                ERR_TEMPLATE_NEEDS_DRAW
+            );
+        }
+    }
+
+    /// Loads a sport and fails if it is not registered.
+    /// Parameters:
+    /// - sport_id: The ID of the sport.
+    fn require_sport(&self, sport_id: SportId) -> SportConfig<Self::Api> {
+        require!(!self.sports(sport_id).is_empty(), ERR_SPORT_NOT_FOUND);
+        self.sports(sport_id).get()
+    }
+
+    /// Fails unless the sport is registered and enabled.
+    /// Parameters:
+    /// - sport_id: The ID of the sport.
+    fn require_sport_enabled(&self, sport_id: SportId) {
+        require!(self.require_sport(sport_id).enabled, ERR_SPORT_DISABLED);
+    }
+
+    /// Fails if the score cannot be a final result of the sport, e.g. a draw where draws are impossible.
+    /// Parameters:
+    /// - sport_id: The ID of the sport.
+    /// - score_home: The score of the home side.
+    /// - score_away: The score of the away side.
+    fn validate_score(&self, sport_id: SportId, score_home: u32, score_away: u32) {
+        let sport = self.require_sport(sport_id);
+        require!(
+            sport.result_format != ResultFormat::ScoreNoDraw || score_home != score_away,
+            ERR_INVALID_SCORE
+        );
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(getSport)]
+    fn get_sport(&self, sport_id: SportId) -> SportConfig<Self::Api> {
+        self.require_sport(sport_id)
+    }
+
+    #[view(getSports)]
+    fn get_sports(&self) -> MultiValueEncoded<SportConfig<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for sport_id in self.sport_ids().iter() {
+            result.push(self.sports(sport_id).get());
+        }
+        result
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    errors::{ERR_EVENT_ALREADY_EXISTS, ERR_EVENT_NOT_FOUND, ERR_EVENT_NOT_OPEN, ERR_INVALID_EVENT_STATUS},
+    types::{sport_index, MarketStatus, Role, SportId, SportEvent, SportEventStatus}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait SportEventModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::sport::SportModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Event Registry --------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Registers an event that markets can then be created for (market creator role).
+    /// Parameters:
+    /// - sport: The ID of an enabled sport from the sport registry.
+    /// - event_id: The unique ID of the event within the sport.
+    /// - competition_id: The ID of the competition (league, tournament) the event belongs to.
+    /// - home_participant: The name or ID of the home team or player.
+    /// - away_participant: The name or ID of the away team or player.
+    /// - scheduled_start: The timestamp when the event is scheduled to start.
+    #[endpoint(createEvent)]
+    fn create_event(
+        &self,
+        sport: SportId,
+        event_id: u64,
+        competition_id: u64,
+        home_participant: ManagedBuffer,
+        away_participant: ManagedBuffer,
+        scheduled_start: u64,
+    ) {
+        self.require_role(Role::MarketCreator);
+        self.require_sport_enabled(sport);
+        require!(self.sport_events(sport, event_id).is_empty(), ERR_EVENT_ALREADY_EXISTS);
+
+        let event = SportEvent {
+            event_id,
+            sport,
+            competition_id,
+            home_participant,
+            away_participant,
+            scheduled_start,
+            status: SportEventStatus::Scheduled,
+            created_at: self.blockchain().get_block_timestamp(),
+        };
+        self.sport_events(sport, event_id).set(&event);
+        self.sport_event_ids(sport).insert(event_id);
+        self.competition_event_ids(sport, competition_id).insert(event_id);
+
+        self.sport_event_created_event(
+            sport_index(sport),
+            event_id,
+            competition_id,
+            &event.home_participant,
+            &event.away_participant,
+            scheduled_start
+        );
+    }
+
+    /// Moves an event through its lifecycle (market creator role).
+    /// Allowed transitions: Scheduled -> Live/Postponed/Cancelled, Live -> Cancelled,
+    /// Postponed -> Cancelled. Use rescheduleEvent to bring a postponed event back to Scheduled;
+    /// setEventScore marks the event Finished.
+    /// Postponing or cancelling the event suspends its open markets and cancels their unmatched orders.
+    /// Matched bets stay in place: a postponed event's markets can be resumed once it is rescheduled,
+    /// a cancelled event's markets are voided by a resolver once closed.
+    /// Parameters:
+    /// - sport: The type of sport.
+    /// - event_id: The ID of the event.
+    /// - status: The new status.
+    #[endpoint(setEventStatus)]
+    fn set_event_status(&self, sport: SportId, event_id: u64, status: SportEventStatus) {
+        self.require_role(Role::MarketCreator);
+        let mut event = self.require_event(sport, event_id);
+
+        let allowed = matches!(
+            (event.status, status),
+            (SportEventStatus::Scheduled, SportEventStatus::Live)
+                | (SportEventStatus::Scheduled, SportEventStatus::Postponed)
+                | (SportEventStatus::Scheduled, SportEventStatus::Cancelled)
+                | (SportEventStatus::Live, SportEventStatus::Cancelled)
+                | (SportEventStatus::Postponed, SportEventStatus::Cancelled)
+        );
+        require!(allowed, ERR_INVALID_EVENT_STATUS);
+
+        event.status = status;
+        self.save_event_status(&event);
+
+        if status == SportEventStatus::Postponed || status == SportEventStatus::Cancelled {
+            self.suspend_event_markets(sport, event_id);
+        }
+    }
+
+    /// Sets a new start time for a scheduled or postponed event, putting it back to Scheduled (market creator role).
+    /// Parameters:
+    /// - sport: The type of sport.
+    /// - event_id: The ID of the event.
+    /// - scheduled_start: The new start timestamp.
+    #[endpoint(rescheduleEvent)]
+    fn reschedule_event(&self, sport: SportId, event_id: u64, scheduled_start: u64) {
+        self.require_role(Role::MarketCreator);
+        let mut event = self.require_event(sport, event_id);
+        require!(
+            event.status == SportEventStatus::Scheduled || event.status == SportEventStatus::Postponed,
+            ERR_INVALID_EVENT_STATUS
+        );
+
+        event.status = SportEventStatus::Scheduled;
+        event.scheduled_start = scheduled_start;
+        self.save_event_status(&event);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Loads an event and fails if it is not registered.
+    /// Parameters:
+    /// - sport: The type of sport.
+    /// - event_id: The ID of the event.
+    fn require_event(&self, sport: SportId, event_id: u64) -> SportEvent<Self::Api> {
+        require!(!self.sport_events(sport, event_id).is_empty(), ERR_EVENT_NOT_FOUND);
+        self.sport_events(sport, event_id).get()
+    }
+
+    /// Fails unless the event is registered and still scheduled or live, so markets can be offered on it.
+    /// Parameters:
+    /// - sport: The type of sport.
+    /// - event_id: The ID of the event.
+    fn require_event_open(&self, sport: SportId, event_id: u64) {
+        let event = self.require_event(sport, event_id);
+        require!(
+            event.status == SportEventStatus::Scheduled || event.status == SportEventStatus::Live,
+            ERR_EVENT_NOT_OPEN
+        );
+    }
+
+    /// Suspends every open market of an event and schedules the cancellation of their unmatched orders.
+    /// Parameters:
+    /// - sport: The type of sport.
+    /// - event_id: The ID of the event.
+    fn suspend_event_markets(&self, sport: SportId, event_id: u64) {
+        for market_id in self.markets_by_event_and_sport(sport, event_id).get().iter() {
+            if self.markets(market_id).get().market_status == MarketStatus::Open {
+                self.suspend_open_market(market_id, true);
+            }
+        }
+    }
+
+    /// Suspends an open market, optionally scheduling the cancellation of every unmatched order.
+    /// Orders are cancelled in resumable batches via cancelUnmatchedBatch, so suspending costs the same
+    /// gas whatever the depth of the book.
+    /// Parameters:
+    /// - market_id: The ID of the market to suspend.
+    /// - cancel_unmatched: Whether to cancel and refund all unmatched orders.
+    fn suspend_open_market(&self, market_id: u64, cancel_unmatched: bool) {
+        let mut market = self.markets(market_id).get();
+        market.market_status = MarketStatus::Suspended;
+        self.markets(market_id).set(&market);
+
+        if cancel_unmatched {
+            self.cancel_cursor(market_id).set(self.market_bet_ids(market_id).len());
+        }
+
+        self.market_suspended_event(market_id, cancel_unmatched, self.blockchain().get_block_timestamp());
+    }
+
+    fn save_event_status(&self, event: &SportEvent<Self::Api>) {
+        self.sport_events(event.sport, event.event_id).set(event);
+        self.sport_event_status_changed_event(
+            sport_index(event.sport),
+            event.event_id,
+            event.status as u8,
+            event.scheduled_start
+        );
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    #[view(getEvent)]
+    fn get_event(&self, sport: SportId, event_id: u64) -> SportEvent<Self::Api> {
+        self.require_event(sport, event_id)
+    }
+
+    #[view(getEventsBySport)]
+    fn get_events_by_sport(&self, sport: SportId) -> MultiValueEncoded<SportEvent<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for event_id in self.sport_event_ids(sport).iter() {
+            result.push(self.sport_events(sport, event_id).get());
+        }
+        result
+    }
+
+    #[view(getEventsByCompetition)]
+    fn get_events_by_competition(&self, sport: SportId, competition_id: u64) -> MultiValueEncoded<SportEvent<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for event_id in self.competition_event_ids(sport, competition_id).iter() {
+            result.push(self.sport_events(sport, event_id).get());
+        }
+        result
+    }
+
+    /// Returns the events of a sport scheduled to start within [from_timestamp, to_timestamp].
+    #[view(getEventsByDate)]
+    fn get_events_by_date(
+        &self,
+        sport: SportId,
+        from_timestamp: u64,
+        to_timestamp: u64,
+    ) -> MultiValueEncoded<SportEvent<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for event_id in self.sport_event_ids(sport).iter() {
+            let event = self.sport_events(sport, event_id).get();
+            if event.scheduled_start >= from_timestamp && event.scheduled_start <= to_timestamp {
+                result.push(event);
+            }
+        }
+        result
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::{Bet, Market, PriceLevel, Sport, Tracker};
+use crate::types::{Bet, BetType, BreakerWindow, CircuitBreakerConfig, LegacyPriceLevel, LimitPeriod, Market, MarketType, Parlay, PendingUserLimits, PeriodUsage, Proposal, SportConfig, SportEvent, ReferenceOdds, Role, SportId, UserLimits, VaultStrategy};
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
@@ -14,22 +14,35 @@ pub trait StorageModule {
     #[storage_mapper("next_bet_id")]
     fn next_bet_id(&self) -> SingleValueMapper<u64>;
 
-    #[storage_mapper("bet_hash_to_id")]
+    #[storage_mapper("bet_hash_ids")]
     fn bet_hash_to_id(&self) -> MapMapper<ManagedBuffer<Self::Api>, u64>;
 
+    #[storage_mapper("bet_hash_to_id")]
+    fn legacy_bet_hash_to_id(&self) -> MapMapper<ManagedBuffer<Self::Api>, u64>;
+
+    #[storage_mapper("bet_id_to_hash")]
+    fn bet_id_to_hash(&self, bet_id: u64) -> SingleValueMapper<ManagedBuffer<Self::Api>>;
+
     #[storage_mapper("marketBetIds")]
     fn market_bet_ids(&self, market_id: u64) -> UnorderedSetMapper<u64>;
 
     #[storage_mapper("betNftToken")]
     fn bet_nft_token(&self) -> NonFungibleTokenMapper<Self::Api>;
 
-    #[storage_mapper("selection_back_levels")]
-    fn selection_back_levels(&self, market_id: u64, selection_id: u64)
-        -> SingleValueMapper<ManagedVec<Self::Api, PriceLevel<Self::Api>>>;
+    #[storage_mapper("order_queue")]
+    fn order_queue(&self, market_id: u64, selection_id: u64, bet_type: BetType, odds: &BigUint)
+        -> LinkedListMapper<u64>;
 
-    #[storage_mapper("selection_lay_levels")]
-    fn selection_lay_levels(&self, market_id: u64, selection_id: u64)
-        -> SingleValueMapper<ManagedVec<Self::Api, PriceLevel<Self::Api>>>;
+    #[storage_mapper("order_node")]
+    fn order_node(&self, bet_id: u64) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("level_stake")]
+    fn level_stake(&self, market_id: u64, selection_id: u64, bet_type: BetType, odds: &BigUint)
+        -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("price_levels")]
+    fn price_levels(&self, market_id: u64, selection_id: u64, bet_type: BetType)
+        -> UnorderedSetMapper<BigUint<Self::Api>>;
 
     #[storage_mapper("selection_back_liquidity")]
     fn selection_back_liquidity(&self, market_id: u64, selection_id: u64)
@@ -63,9 +76,9 @@ pub trait StorageModule {
     fn selection_canceled_count(&self, market_id: u64, selection_id: u64)
         -> SingleValueMapper<u64>;
 
-    #[storage_mapper("selection_tracker")]
-    fn selection_tracker(&self, market_id: u64, selection_id: u64)
-        -> SingleValueMapper<Tracker<Self::Api>>;
+    #[storage_mapper("selection_void_count")]
+    fn selection_void_count(&self, market_id: u64, selection_id: u64)
+        -> SingleValueMapper<u64>;
 
     #[storage_mapper("markets")]
     fn markets(&self, market_id: u64) -> SingleValueMapper<Market<Self::Api>>;
@@ -75,19 +88,253 @@ pub trait StorageModule {
         -> SingleValueMapper<BigUint<Self::Api>>;
 
     #[storage_mapper("locked_funds")]
-    fn locked_funds(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint<Self::Api>>;
+    fn locked_funds(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier)
+        -> SingleValueMapper<BigUint<Self::Api>>;
 
-    #[storage_mapper("marketsByEvent")]
-    fn markets_by_event(&self, event_id: u64) -> SingleValueMapper<ManagedVec<u64>>;
+    #[storage_mapper("locked_funds")]
+    fn legacy_locked_funds(&self, address: &ManagedAddress) -> SingleValueMapper<BigUint<Self::Api>>;
 
     #[storage_mapper("winningSelection")]
     fn winning_selection(&self, market_id: u64) -> SingleValueMapper<u64>;
 
+    #[storage_mapper("event_score_by_sport")]
+    fn event_score(&self, sport: SportId, event_id: u64) -> SingleValueMapper<(u32, u32)>;
+
     #[storage_mapper("event_score")]
-    fn event_score(&self, event_id: u64) -> SingleValueMapper<(u32, u32)>;
+    fn legacy_event_score(&self, event_id: u64) -> SingleValueMapper<(u32, u32)>;
+
+    #[storage_mapper("next_market_id")]
+    fn next_market_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("next_selection_id")]
+    fn next_selection_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("market_id_lookup")]
+    fn market_id_lookup(&self, sport: SportId, event_id: u64, market_type: MarketType, line: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("open_markets_by_sport")]
+    fn open_markets_by_sport(&self, sport: SportId) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("open_markets_by_close_bucket")]
+    fn open_markets_by_close_bucket(&self, bucket: u64) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("open_close_buckets")]
+    fn open_close_buckets(&self) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("settled_markets")]
+    fn settled_markets(&self) -> VecMapper<u64>;
+
+    #[storage_mapper("market_line")]
+    fn market_line(&self, market_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("sports")]
+    fn sports(&self, sport: SportId) -> SingleValueMapper<SportConfig<Self::Api>>;
+
+    #[storage_mapper("sport_ids")]
+    fn sport_ids(&self) -> UnorderedSetMapper<SportId>;
+
+    #[storage_mapper("sport_events")]
+    fn sport_events(&self, sport: SportId, event_id: u64) -> SingleValueMapper<SportEvent<Self::Api>>;
+
+    #[storage_mapper("sport_event_ids")]
+    fn sport_event_ids(&self, sport: SportId) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("competition_event_ids")]
+    fn competition_event_ids(&self, sport: SportId, competition_id: u64) -> UnorderedSetMapper<u64>;
 
     #[storage_mapper("markets_by_event_and_sport")]
-    fn markets_by_event_and_sport(&self, sport: Sport, event_id: u64) -> SingleValueMapper<ManagedVec<Self::Api, u64>>;
+    fn markets_by_event_and_sport(&self, sport: SportId, event_id: u64) -> SingleValueMapper<ManagedVec<Self::Api, u64>>;
 
-}
+    #[storage_mapper("market_in_play")]
+    fn market_in_play(&self, market_id: u64) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("market_bet_delay")]
+    fn market_bet_delay(&self, market_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("delayed_bets")]
+    fn delayed_bets(&self, market_id: u64) -> QueueMapper<u64>;
+
+    #[storage_mapper("refund_cursor")]
+    fn refund_cursor(&self, market_id: u64) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("cancel_cursor")]
+    fn cancel_cursor(&self, market_id: u64) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("settlement_cursor")]
+    fn settlement_cursor(&self, market_id: u64) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("claimable_balance")]
+    fn claimable_balance(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier)
+        -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("claimable_tokens")]
+    fn claimable_tokens(&self, address: &ManagedAddress)
+        -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier<Self::Api>>;
+
+    #[storage_mapper("keeper_reward")]
+    fn keeper_reward(&self) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("keeper_reward_pool")]
+    fn keeper_reward_pool(&self) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("market_sport_index")]
+    fn market_sport_index(&self, market_id: u64) -> SingleValueMapper<u8>;
+
+    #[storage_mapper("user_open_orders")]
+    fn user_open_orders(&self, address: &ManagedAddress, market_id: u64) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("user_escrowed_bets")]
+    fn user_escrowed_bets(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("total_locked_funds")]
+    fn total_locked_funds(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("total_claimable_balance")]
+    fn total_claimable_balance(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("total_matched_escrow")]
+    fn total_matched_escrow(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("escrow_dust")]
+    fn escrow_dust(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("market_void")]
+    fn market_void(&self, market_id: u64) -> SingleValueMapper<bool>;
 
+    #[storage_mapper("parlays")]
+    fn parlays(&self, parlay_id: u64) -> SingleValueMapper<Parlay<Self::Api>>;
+
+    #[storage_mapper("next_parlay_id")]
+    fn next_parlay_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("user_parlays")]
+    fn user_parlays(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("parlay_leg_odds")]
+    fn parlay_leg_odds(&self, market_id: u64, selection_id: u64) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("house_pool")]
+    fn house_pool(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("house_reserved")]
+    fn house_reserved(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("vault_share_token")]
+    fn vault_share_token(&self) -> FungibleTokenMapper<Self::Api>;
+
+    #[storage_mapper("vault_share_supply")]
+    fn vault_share_supply(&self) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("vault_token")]
+    fn vault_token(&self) -> SingleValueMapper<EgldOrEsdtTokenIdentifier<Self::Api>>;
+
+    #[storage_mapper("vault_keeper")]
+    fn vault_keeper(&self) -> SingleValueMapper<ManagedAddress<Self::Api>>;
+
+    #[storage_mapper("vault_strategy")]
+    fn vault_strategy(&self, market_id: u64) -> SingleValueMapper<VaultStrategy<Self::Api>>;
+
+    #[storage_mapper("vault_market_exposure")]
+    fn vault_market_exposure(&self, market_id: u64) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("vault_markets")]
+    fn vault_markets(&self) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("odds_oracle")]
+    fn odds_oracle(&self) -> SingleValueMapper<ManagedAddress<Self::Api>>;
+
+    #[storage_mapper("reference_odds")]
+    fn reference_odds(&self, market_id: u64, selection_id: u64) -> SingleValueMapper<ReferenceOdds<Self::Api>>;
+
+    #[storage_mapper("max_odds_deviation_bps")]
+    fn max_odds_deviation_bps(&self, market_id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("user_limits")]
+    fn user_limits(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier)
+        -> SingleValueMapper<UserLimits<Self::Api>>;
+
+    #[storage_mapper("pending_user_limits")]
+    fn pending_user_limits(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier)
+        -> SingleValueMapper<PendingUserLimits<Self::Api>>;
+
+    #[storage_mapper("user_period_usage")]
+    fn user_period_usage(
+        &self,
+        address: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        period: LimitPeriod,
+        period_index: u64,
+    ) -> SingleValueMapper<PeriodUsage<Self::Api>>;
+
+    #[storage_mapper("outstanding_max_loss")]
+    fn outstanding_max_loss(&self, address: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier)
+        -> SingleValueMapper<BigUint<Self::Api>>;
+
+    #[storage_mapper("self_excluded_until")]
+    fn self_excluded_until(&self, address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("role_members")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress<Self::Api>>;
+
+    #[storage_mapper("required_approvals")]
+    fn required_approvals(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("timelock_delay")]
+    fn timelock_delay(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("proposals")]
+    fn proposals(&self, proposal_id: u64) -> SingleValueMapper<Proposal<Self::Api>>;
+
+    #[storage_mapper("next_proposal_id")]
+    fn next_proposal_id(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("pending_proposals")]
+    fn pending_proposals(&self) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("proposal_approvals")]
+    fn proposal_approvals(&self, proposal_id: u64) -> UnorderedSetMapper<ManagedAddress<Self::Api>>;
+
+    #[storage_mapper("pending_result_corrections")]
+    fn pending_result_corrections(&self, market_id: u64) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("guardian")]
+    fn guardian(&self) -> SingleValueMapper<ManagedAddress<Self::Api>>;
+
+    #[storage_mapper("global_paused")]
+    fn global_paused(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("market_paused")]
+    fn market_paused(&self, market_id: u64) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("sport_paused")]
+    fn sport_paused(&self, sport: SportId) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("circuit_breaker_config")]
+    fn circuit_breaker_config(&self) -> SingleValueMapper<CircuitBreakerConfig<Self::Api>>;
+
+    #[storage_mapper("breaker_window")]
+    fn breaker_window(&self, market_id: u64, selection_id: u64) -> SingleValueMapper<BreakerWindow<Self::Api>>;
+
+    #[storage_mapper("storage_version")]
+    fn storage_version(&self) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("migration_cursor")]
+    fn migration_cursor(&self, market_id: u64) -> SingleValueMapper<usize>;
+
+    #[storage_mapper("migration_started")]
+    fn migration_started(&self, market_id: u64) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("market_storage_version")]
+    fn market_storage_version(&self, market_id: u64) -> SingleValueMapper<u32>;
+
+    #[storage_mapper("selection_back_levels")]
+    fn legacy_back_levels(&self, market_id: u64, selection_id: u64)
+        -> SingleValueMapper<ManagedVec<Self::Api, LegacyPriceLevel<Self::Api>>>;
+
+    #[storage_mapper("selection_lay_levels")]
+    fn legacy_lay_levels(&self, market_id: u64, selection_id: u64)
+        -> SingleValueMapper<ManagedVec<Self::Api, LegacyPriceLevel<Self::Api>>>;
+
+    #[storage_mapper("selection_tracker")]
+    fn legacy_selection_tracker(&self, market_id: u64, selection_id: u64) -> SingleValueMapper<ManagedBuffer<Self::Api>>;
+}
@@ -1,3 +1,5 @@
+use crate::constants::constants::{BET_ENCODING_VERSION, MARKET_ENCODING_VERSION};
+use multiversx_sc::codec::{DecodeErrorHandler, EncodeErrorHandler, NestedDecodeInput, TopDecodeInput, TopEncodeOutput};
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
@@ -10,6 +12,8 @@ pub enum BetStatus {
     Win,
     Lost,
     Claimed,
+    Pending,
+    Void,
 }
 
 #[type_abi]
@@ -19,14 +23,63 @@ pub enum BetType {
     Lay
 }
 
+/// Sport identifier from the sport registry. It encodes exactly like the former `Sport` enum,
+/// so the built-in sports keep their values (0 = Football ... 4 = LeagueOfLegends).
+pub type SportId = u8;
+
+/// Returns the 1-based index that events have always carried for a sport (1 = Football ...
+/// 5 = LeagueOfLegends), so indexers keep reading the same values from the registry IDs.
+/// Parameters:
+/// - sport_id: The ID of the sport, below SportId::MAX.
+pub fn sport_index(sport_id: SportId) -> u8 {
+    sport_id + 1
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum ResultFormat {
+    Score,
+    ScoreNoDraw,
+}
+
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, ManagedVecItem, Copy)]
-pub enum Sport {
-    Football,
-    Basketball,
-    CounterStrike,
-    Dota,
-    LeagueOfLegends,
+pub struct MarketTemplate {
+    pub market_type: MarketType,
+    pub line: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SportConfig<M: ManagedTypeApi> {
+    pub sport_id: SportId,
+    pub name: ManagedBuffer<M>,
+    pub market_templates: ManagedVec<M, MarketTemplate>,
+    pub result_format: ResultFormat,
+    pub enabled: bool,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum SportEventStatus {
+    Scheduled,
+    Live,
+    Finished,
+    Postponed,
+    Cancelled,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SportEvent<M: ManagedTypeApi> {
+    pub event_id: u64,
+    pub sport: SportId,
+    pub competition_id: u64,
+    pub home_participant: ManagedBuffer<M>,
+    pub away_participant: ManagedBuffer<M>,
+    pub scheduled_start: u64,
+    pub status: SportEventStatus,
+    pub created_at: u64,
 }
 
 #[type_abi]
@@ -34,7 +87,8 @@ pub enum Sport {
 pub enum MarketStatus {
     Open,    
     Closed, 
-    Settled
+    Settled,
+    Suspended
 }
 
 #[type_abi]
@@ -50,13 +104,13 @@ pub struct MatchedPart<M: ManagedTypeApi> {
 }
 
 #[type_abi]
-#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+#[derive(NestedEncode, NestedDecode, Clone)]
 pub struct Bet<M: ManagedTypeApi> {
     pub bet_id: u64,
     pub bettor: ManagedAddress<M>,
-    pub sport: Sport,
+    pub sport: SportId,
     pub event: u64,
-    pub selection: Selection<M>,
+    pub selection: Selection,
     pub stake_amount: BigUint<M>,
     pub liability: BigUint<M>,
     pub total_amount: BigUint<M>,
@@ -75,7 +129,7 @@ pub struct Bet<M: ManagedTypeApi> {
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
 pub struct BetAttributes<M:ManagedTypeApi>{
     pub event: u64,     
-    pub selection: Selection<M>,     
+    pub selection: Selection,     
     pub stake: BigUint<M>, 
     pub potential_win: BigUint<M>,     
     pub odd: BigUint<M>,        
@@ -83,13 +137,14 @@ pub struct BetAttributes<M:ManagedTypeApi>{
     pub status: BetStatus,
 }
 
-#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+#[type_abi]
+#[derive(NestedEncode, NestedDecode, Clone)]
 pub struct Market<M: ManagedTypeApi> {
     pub market_id: u64,
     pub event_id: u64,
     pub description: ManagedBuffer<M>,
     pub market_type: MarketType, 
-    pub selections: ManagedVec<M, Selection<M>>,
+    pub selections: ManagedVec<M, Selection>,
     pub close_timestamp: u64,
     pub market_status: MarketStatus,
     pub total_matched_amount: BigUint<M>,
@@ -158,10 +213,9 @@ pub struct SelectionInfo {
 
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem)]
-pub struct Selection<M: ManagedTypeApi> {
+pub struct Selection {
     pub id: u64,
     pub selection_type: SelectionType,
-    pub priority_queue: Tracker<M>,
 }
 
 #[type_abi]
@@ -173,28 +227,79 @@ pub struct MarketSelectionInfo<M: ManagedTypeApi> {
 }
 
 #[type_abi]
-#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem)]
-pub struct PriceLevel<M: ManagedTypeApi> {
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct BetView<M: ManagedTypeApi> {
+    pub bet_id: u64,
+    pub bettor: ManagedAddress<M>,
+    pub sport: SportId,
+    pub market_id: u64,
+    pub selection_id: u64,
+    pub selection_type: SelectionType,
+    pub bet_type: BetType,
+    pub status: BetStatus,
     pub odds: BigUint<M>,
-    pub total_stake: BigUint<M>,
-    pub bet_ids: ManagedVec<M, u64>,
+    pub stake_amount: BigUint<M>,
+    pub liability: BigUint<M>,
+    pub total_amount: BigUint<M>,
+    pub total_matched: BigUint<M>,
+    pub potential_profit: BigUint<M>,
+    pub matched_parts: ManagedVec<M, MatchedPart<M>>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub payment_nonce: u64,
+    pub nft_nonce: u64,
+    pub created_at: u64,
 }
 
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem)]
-pub struct Tracker<M: ManagedTypeApi> {
-    pub back_levels: ManagedVec<M, PriceLevel<M>>,
-    pub lay_levels: ManagedVec<M, PriceLevel<M>>,
+pub struct SelectionState<M: ManagedTypeApi> {
+    pub selection_id: u64,
+    pub selection_type: SelectionType,
     pub back_liquidity: BigUint<M>,
     pub lay_liquidity: BigUint<M>,
-    pub matched_count: u64,
+    pub matched_amount: BigUint<M>,
+    pub best_back_odds: BigUint<M>,
+    pub best_lay_odds: BigUint<M>,
+    pub back_price_levels: u32,
+    pub lay_price_levels: u32,
     pub unmatched_count: u64,
     pub partially_matched_count: u64,
+    pub matched_count: u64,
     pub win_count: u64,
     pub lost_count: u64,
+    pub void_count: u64,
     pub canceled_count: u64,
 }
 
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct MarketView<M: ManagedTypeApi> {
+    pub market_id: u64,
+    pub sport: SportId,
+    pub event_id: u64,
+    pub market_type: MarketType,
+    pub description: ManagedBuffer<M>,
+    pub line: u64,
+    pub status: MarketStatus,
+    pub in_play: bool,
+    pub close_timestamp: u64,
+    pub total_matched_amount: BigUint<M>,
+    pub winning_selection: u64,
+    pub is_void: bool,
+    pub selections: ManagedVec<M, SelectionState<M>>,
+    pub created_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct EventView<M: ManagedTypeApi> {
+    pub event: SportEvent<M>,
+    pub has_score: bool,
+    pub score_home: u32,
+    pub score_away: u32,
+    pub market_ids: ManagedVec<M, u64>,
+}
+
 #[type_abi]
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, ManagedVecItem, Copy)]
 pub enum MarketType {
@@ -204,6 +309,184 @@ pub enum MarketType {
     Winner,
 }
 
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, ManagedVecItem, Copy)]
+pub enum ParlayStatus {
+    Open,
+    Won,
+    Lost,
+    Void,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem)]
+pub struct ParlayLeg<M: ManagedTypeApi> {
+    pub market_id: u64,
+    pub selection_id: u64,
+    pub odds: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct Parlay<M: ManagedTypeApi> {
+    pub parlay_id: u64,
+    pub bettor: ManagedAddress<M>,
+    pub legs: ManagedVec<M, ParlayLeg<M>>,
+    pub stake_amount: BigUint<M>,
+    pub combined_odds: BigUint<M>,
+    pub potential_payout: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub status: ParlayStatus,
+    pub created_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum Role {
+    Admin,
+    MarketCreator,
+    Resolver,
+    RiskManager,
+    Treasury,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub enum ProposalAction<M: ManagedTypeApi> {
+    SetKeeperReward(BigUint<M>),
+    SetCircuitBreaker(CircuitBreakerConfig<M>),
+    SetMaxOddsDeviation(u64, u64),
+    SetGuardian(ManagedAddress<M>),
+    SetOddsOracle(ManagedAddress<M>),
+    GrantRole(Role, ManagedAddress<M>),
+    RevokeRole(Role, ManagedAddress<M>),
+    CorrectMarketResult(u64, u64),
+    SetGovernanceConfig(u64, u64),
+    WithdrawHouse(EgldOrEsdtTokenIdentifier<M>, BigUint<M>, ManagedAddress<M>),
+    SetVaultToken(EgldOrEsdtTokenIdentifier<M>),
+    SetVaultKeeper(ManagedAddress<M>),
+    SetVaultStrategy(u64, VaultStrategy<M>),
+    SetParlayLegOdds(u64, u64, BigUint<M>),
+    SetSportTemplates(SportId, ManagedVec<M, MarketTemplate>),
+    SetSportEnabled(SportId, bool),
+    VoidMarket(u64),
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Cancelled,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct Proposal<M: ManagedTypeApi> {
+    pub proposal_id: u64,
+    pub proposer: ManagedAddress<M>,
+    pub action: ProposalAction<M>,
+    pub created_at: u64,
+    pub executable_at: u64,
+    pub status: ProposalStatus,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct CircuitBreakerConfig<M: ManagedTypeApi> {
+    pub window_seconds: u64,
+    pub max_matched_volume: BigUint<M>,
+    pub max_price_move_bps: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct BreakerWindow<M: ManagedTypeApi> {
+    pub window_start: u64,
+    pub matched_volume: BigUint<M>,
+    pub start_odds: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum LimitPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct UserLimits<M: ManagedTypeApi> {
+    pub daily_deposit_limit: BigUint<M>,
+    pub weekly_deposit_limit: BigUint<M>,
+    pub monthly_deposit_limit: BigUint<M>,
+    pub daily_loss_limit: BigUint<M>,
+    pub weekly_loss_limit: BigUint<M>,
+    pub monthly_loss_limit: BigUint<M>,
+    pub max_stake: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct PendingUserLimits<M: ManagedTypeApi> {
+    pub limits: UserLimits<M>,
+    pub effective_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct PeriodUsage<M: ManagedTypeApi> {
+    pub deposited: BigUint<M>,
+    pub lost: BigUint<M>,
+    pub won: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct ReferenceOdds<M: ManagedTypeApi> {
+    pub odds: BigUint<M>,
+    pub updated_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct VaultStrategy<M: ManagedTypeApi> {
+    pub spread_bps: u64,
+    pub quote_amount: BigUint<M>,
+    pub max_exposure: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum InvariantKind {
+    SideLiquidity,
+    LevelStake,
+    QueuedBet,
+    LockedFunds,
+    ContractBalance,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SelectionViolation<M: ManagedTypeApi> {
+    pub kind: InvariantKind,
+    pub bet_type: BetType,
+    pub odds: BigUint<M>,
+    pub bet_id: u64,
+    pub expected: BigUint<M>,
+    pub actual: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct BalanceViolation<M: ManagedTypeApi> {
+    pub kind: InvariantKind,
+    pub token: EgldOrEsdtTokenIdentifier<M>,
+    pub expected: BigUint<M>,
+    pub actual: BigUint<M>,
+}
+
 impl MarketType {
     pub fn from_u64(value: u64) -> Self {
         match value {
@@ -224,6 +507,15 @@ impl MarketType {
         }
     }
 
+    pub fn selection_types(&self) -> &'static [SelectionType] {
+        match self {
+            MarketType::FullTimeResult => &[SelectionType::One, SelectionType::Draw, SelectionType::Two],
+            MarketType::TotalGoals => &[SelectionType::Over, SelectionType::Under],
+            MarketType::BothTeamsToScore => &[SelectionType::Yes, SelectionType::No],
+            MarketType::Winner => &[SelectionType::One, SelectionType::Two],
+        }
+    }
+
     pub fn to_description(&self) -> &[u8] {
         match self {
             MarketType::FullTimeResult => b"Fulltime Result",
@@ -233,3 +525,222 @@ impl MarketType {
         }
     }
 }
+
+//--------------------------------------------------------------------------------------------//
+//-------------------------------- Versioned Encodings ---------------------------------------//
+//--------------------------------------------------------------------------------------------//
+
+// Bets and markets are stored behind a leading encoding version byte, so fields can be added
+// later by bumping the version and decoding the older layouts here. Records written before the
+// encodings were versioned start with their u64 ID, whose high byte is always zero, so a zero
+// version byte identifies that legacy layout.
+
+impl<M: ManagedTypeApi> TopEncode for Bet<M> {
+    fn top_encode_or_handle_err<O, H>(&self, output: O, h: H) -> Result<(), H::HandledErr>
+    where
+        O: TopEncodeOutput,
+        H: EncodeErrorHandler,
+    {
+        let mut buffer = output.start_nested_encode();
+        BET_ENCODING_VERSION.dep_encode_or_handle_err(&mut buffer, h)?;
+        self.dep_encode_or_handle_err(&mut buffer, h)?;
+        output.finalize_nested_encode(buffer);
+        Result::Ok(())
+    }
+}
+
+impl<M: ManagedTypeApi> TopDecode for Bet<M> {
+    fn top_decode_or_handle_err<I, H>(input: I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: TopDecodeInput,
+        H: DecodeErrorHandler,
+    {
+        let mut buffer = input.into_nested_buffer();
+        let bet = match u8::dep_decode_or_handle_err(&mut buffer, h)? {
+            0 => {
+                let bet_id = decode_legacy_id(&mut buffer, h)?;
+                LegacyBetBody::dep_decode_or_handle_err(&mut buffer, h)?.into_bet(bet_id)
+            },
+            BET_ENCODING_VERSION => Self::dep_decode_or_handle_err(&mut buffer, h)?,
+            _ => return Result::Err(h.handle_error(DecodeError::INVALID_VALUE)),
+        };
+        if !buffer.is_depleted() {
+            return Result::Err(h.handle_error(DecodeError::INPUT_TOO_LONG));
+        }
+        Result::Ok(bet)
+    }
+}
+
+impl<M: ManagedTypeApi> TopEncode for Market<M> {
+    fn top_encode_or_handle_err<O, H>(&self, output: O, h: H) -> Result<(), H::HandledErr>
+    where
+        O: TopEncodeOutput,
+        H: EncodeErrorHandler,
+    {
+        let mut buffer = output.start_nested_encode();
+        MARKET_ENCODING_VERSION.dep_encode_or_handle_err(&mut buffer, h)?;
+        self.dep_encode_or_handle_err(&mut buffer, h)?;
+        output.finalize_nested_encode(buffer);
+        Result::Ok(())
+    }
+}
+
+impl<M: ManagedTypeApi> TopDecode for Market<M> {
+    fn top_decode_or_handle_err<I, H>(input: I, h: H) -> Result<Self, H::HandledErr>
+    where
+        I: TopDecodeInput,
+        H: DecodeErrorHandler,
+    {
+        let mut buffer = input.into_nested_buffer();
+        let market = match u8::dep_decode_or_handle_err(&mut buffer, h)? {
+            0 => {
+                let market_id = decode_legacy_id(&mut buffer, h)?;
+                LegacyMarketBody::dep_decode_or_handle_err(&mut buffer, h)?.into_market(market_id)
+            },
+            MARKET_ENCODING_VERSION => Self::dep_decode_or_handle_err(&mut buffer, h)?,
+            _ => return Result::Err(h.handle_error(DecodeError::INVALID_VALUE)),
+        };
+        if !buffer.is_depleted() {
+            return Result::Err(h.handle_error(DecodeError::INPUT_TOO_LONG));
+        }
+        Result::Ok(market)
+    }
+}
+
+/// Reads the seven low bytes of a legacy record ID, the zero high byte having been read as the version.
+fn decode_legacy_id<I, H>(input: &mut I, h: H) -> Result<u64, H::HandledErr>
+where
+    I: NestedDecodeInput,
+    H: DecodeErrorHandler,
+{
+    let mut bytes = [0u8; 8];
+    input.read_into(&mut bytes[1..], h)?;
+    Result::Ok(u64::from_be_bytes(bytes))
+}
+
+//--------------------------------------------------------------------------------------------//
+//-------------------------------- Legacy Layouts --------------------------------------------//
+//--------------------------------------------------------------------------------------------//
+
+// Layouts written before the encodings were versioned, when every selection embedded a copy of
+// its order book. They are only read, to convert the records to the current layout.
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, ManagedVecItem)]
+pub struct LegacyPriceLevel<M: ManagedTypeApi> {
+    pub odds: BigUint<M>,
+    pub total_stake: BigUint<M>,
+    pub bet_ids: ManagedVec<M, u64>,
+}
+
+#[derive(NestedDecode, Clone, ManagedVecItem)]
+pub struct LegacyTracker<M: ManagedTypeApi> {
+    pub back_levels: ManagedVec<M, LegacyPriceLevel<M>>,
+    pub lay_levels: ManagedVec<M, LegacyPriceLevel<M>>,
+    pub back_liquidity: BigUint<M>,
+    pub lay_liquidity: BigUint<M>,
+    pub matched_count: u64,
+    pub unmatched_count: u64,
+    pub partially_matched_count: u64,
+    pub win_count: u64,
+    pub lost_count: u64,
+    pub canceled_count: u64,
+}
+
+#[derive(NestedDecode, Clone, ManagedVecItem)]
+pub struct LegacySelection<M: ManagedTypeApi> {
+    pub id: u64,
+    pub selection_type: SelectionType,
+    pub priority_queue: LegacyTracker<M>,
+}
+
+impl<M: ManagedTypeApi> LegacySelection<M> {
+    pub fn into_selection(self) -> Selection {
+        Selection {
+            id: self.id,
+            selection_type: self.selection_type,
+        }
+    }
+}
+
+/// A legacy bet without its leading ID.
+#[derive(NestedDecode)]
+pub struct LegacyBetBody<M: ManagedTypeApi> {
+    pub bettor: ManagedAddress<M>,
+    pub sport: SportId,
+    pub event: u64,
+    pub selection: LegacySelection<M>,
+    pub stake_amount: BigUint<M>,
+    pub liability: BigUint<M>,
+    pub total_amount: BigUint<M>,
+    pub total_matched: BigUint<M>,
+    pub matched_parts: ManagedVec<M, MatchedPart<M>>,
+    pub potential_profit: BigUint<M>,
+    pub odd: BigUint<M>,
+    pub bet_type: BetType,
+    pub status: BetStatus,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub payment_nonce: u64,
+    pub nft_nonce: u64,
+    pub created_at: u64,
+}
+
+impl<M: ManagedTypeApi> LegacyBetBody<M> {
+    pub fn into_bet(self, bet_id: u64) -> Bet<M> {
+        Bet {
+            bet_id,
+            bettor: self.bettor,
+            sport: self.sport,
+            event: self.event,
+            selection: self.selection.into_selection(),
+            stake_amount: self.stake_amount,
+            liability: self.liability,
+            total_amount: self.total_amount,
+            total_matched: self.total_matched,
+            matched_parts: self.matched_parts,
+            potential_profit: self.potential_profit,
+            odd: self.odd,
+            bet_type: self.bet_type,
+            status: self.status,
+            payment_token: self.payment_token,
+            payment_nonce: self.payment_nonce,
+            nft_nonce: self.nft_nonce,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// A legacy market without its leading ID.
+#[derive(NestedDecode)]
+pub struct LegacyMarketBody<M: ManagedTypeApi> {
+    pub event_id: u64,
+    pub description: ManagedBuffer<M>,
+    pub market_type: MarketType,
+    pub selections: ManagedVec<M, LegacySelection<M>>,
+    pub close_timestamp: u64,
+    pub market_status: MarketStatus,
+    pub total_matched_amount: BigUint<M>,
+    pub liquidity: BigUint<M>,
+    pub created_at: u64,
+}
+
+impl<M: ManagedTypeApi> LegacyMarketBody<M> {
+    pub fn into_market(self, market_id: u64) -> Market<M> {
+        let mut selections = ManagedVec::new();
+        for selection in self.selections.into_iter() {
+            selections.push(selection.into_selection());
+        }
+
+        Market {
+            market_id,
+            event_id: self.event_id,
+            description: self.description,
+            market_type: self.market_type,
+            selections,
+            close_timestamp: self.close_timestamp,
+            market_status: self.market_status,
+            total_matched_amount: self.total_matched_amount,
+            liquidity: self.liquidity,
+            created_at: self.created_at,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::{Sport, BetType};
+use crate::{constants::constants::{CLOSE_BUCKET_SECONDS, NO_LINE, STORAGE_VERSION, TOTAL_GOALS_LINE}, errors::{ERR_DUPLICATE_BET, ERR_INVALID_MARKET}, types::{SportId, BetType, MarketType}};
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
@@ -8,14 +8,15 @@ crate::storage::StorageModule {
     fn generate_unique_bet_hash(
         &self,
         caller: &ManagedAddress<Self::Api>,
-        sport: &Sport,
+        sport: &SportId,
         market_id: &u64,
         selection_id: &u64,
         odds: &BigUint<Self::Api>,
         bet_type: &BetType,
         token_identifier: &EgldOrEsdtTokenIdentifier<Self::Api>,
         token_nonce: u64,
-        amount: &BigUint<Self::Api>
+        amount: &BigUint<Self::Api>,
+        order_index: u64
     ) -> ManagedBuffer<Self::Api> {
         let current_timestamp = self.blockchain().get_block_timestamp();
         let current_nonce = self.blockchain().get_block_nonce();
@@ -24,14 +25,7 @@ crate::storage::StorageModule {
         
         data.append(&caller.as_managed_buffer());
         
-        let sport_value = match sport {
-            Sport::Football => 1u8,
-            Sport::Basketball => 2u8,
-            Sport::CounterStrike => 3u8,
-            Sport::Dota => 4u8,
-            Sport::LeagueOfLegends => 5u8,
-        };
-        data.append_bytes(&[sport_value]);
+        data.append_bytes(&[*sport]);
         data.append(&self.serialize_u64(market_id));
         data.append(&self.serialize_u64(selection_id));
         
@@ -46,6 +40,7 @@ crate::storage::StorageModule {
         data.append(&token_identifier.clone().into_name().clone());
         data.append(&self.serialize_u64(&token_nonce));
         data.append(&amount.to_bytes_be_buffer());
+        data.append(&self.serialize_u64(&order_index));
         
         data.append(&self.serialize_u64(&current_timestamp));
         data.append(&self.serialize_u64(&current_nonce));
@@ -100,42 +95,26 @@ crate::storage::StorageModule {
         output
     }
 
-    fn get_bet_id_hash(&self, bet_hash: &ManagedBuffer<Self::Api>) -> u64 {
-        if self.bet_hash_to_id().contains_key(bet_hash) {
-            return self.bet_hash_to_id().get(bet_hash).unwrap_or_default();
-        }
-        
-        let current_timestamp = self.blockchain().get_block_timestamp();
-        let current_nft_nonce = self.next_bet_id().get();
-        
-        let mut data = ManagedBuffer::new();
-        data.append(bet_hash);
-        data.append(&self.serialize_u64(&current_timestamp));
-        data.append(&self.serialize_u64(&current_nft_nonce));
-        
-        let hash_bytes = self.crypto().sha256(&data);
-        let mut all_bytes = [0u8; 32];
-        hash_bytes.as_managed_buffer().load_to_byte_array(&mut all_bytes);
-        
-        let mut u32_bytes = [0u8; 4];
-        for i in 0..4 {
-            u32_bytes[i] = all_bytes[i];
-        }
-        
-        let raw_id_part = u32::from_be_bytes(u32_bytes);
-        
-        let max_id: u64 = 9_000_000_000; 
-        let id_part = (raw_id_part as u64 % max_id) + 1_000_000_000;
-        
-        let timestamp_part = current_timestamp % 1_000_000;
-        let final_id = id_part + timestamp_part; 
-        
-        self.next_bet_id().set(current_nft_nonce + 1);
-        self.bet_hash_to_id().insert(bet_hash.clone(), final_id);
-        
-        final_id
+    /// Issues the next sequential bet ID and records the bet hash for idempotency.
+    /// An identical bet (same caller, selection, odds, amount, block and position in the call) is rejected
+    /// instead of overwriting the first one.
+    /// Parameters:
+    /// - bet_hash: The hash returned by generate_unique_bet_hash.
+    /// Returns: The new bet ID.
+    fn allocate_bet_id(&self, bet_hash: &ManagedBuffer<Self::Api>) -> u64 {
+        require!(!self.bet_hash_to_id().contains_key(bet_hash), ERR_DUPLICATE_BET);
+
+        let bet_id = self.next_bet_id().get() + 1;
+        self.next_bet_id().set(bet_id);
+        self.bet_hash_to_id().insert(bet_hash.clone(), bet_id);
+        self.bet_id_to_hash(bet_id).set(bet_hash);
+
+        bet_id
     }
 
+    /// Deletes a bet and its index entries. The bet must already be out of the order book (see remove_from_orderbook).
+    /// Parameters:
+    /// - bet_id: The ID of the bet.
     fn delete_bet(&self, bet_id: u64) {
         // Verifică dacă bet-ul există
         if self.bet_by_id(bet_id).is_empty() {
@@ -148,107 +127,110 @@ crate::storage::StorageModule {
         self.market_bet_ids(bet.event).swap_remove(&bet_id);
         
         // 2. Șterge din bet_nonce_to_id
-        self.bet_nonce_to_id(bet.nft_nonce).clear();
-        
-        // 3. Curăță din selection_back_levels sau selection_lay_levels
-        // În funcție de tipul bet-ului
-        match bet.bet_type {
-            BetType::Back => {
-                let mut back_levels = self.selection_back_levels(bet.event, bet.selection.id).get();
-                let mut updated = false;
-                
-                for i in 0..back_levels.len() {
-                    let mut level = back_levels.get(i);
-                    let mut new_bet_ids = ManagedVec::new();
-                    
-                    for level_bet_id in level.bet_ids.iter() {
-                        if level_bet_id != bet_id {
-                            new_bet_ids.push(level_bet_id);
-                        } else {
-                            updated = true;
-                        }
-                    }
-                    
-                    if new_bet_ids.is_empty() {
-                        // Elimină nivelul complet dacă nu mai conține pariuri
-                        if i < back_levels.len() - 1 {
-                            let last = back_levels.get(back_levels.len() - 1);
-                            let _ = back_levels.set(i, last);
-                        }
-                        back_levels.remove(back_levels.len() - 1);
-                        updated = true;
-                    } else {
-                        level.bet_ids = new_bet_ids;
-                        let _ = back_levels.set(i, level);
-                    }
-                }
-                
-                if updated {
-                    self.selection_back_levels(bet.event, bet.selection.id).set(&back_levels);
-                    
-                    // Recalculează lichiditatea totală
-                    let mut total_liquidity = BigUint::zero();
-                    for level in back_levels.iter() {
-                        total_liquidity += &level.total_stake;
-                    }
-                    self.selection_back_liquidity(bet.event, bet.selection.id).set(&total_liquidity);
-                }
-            },
-            BetType::Lay => {
-                let mut lay_levels = self.selection_lay_levels(bet.event, bet.selection.id).get();
-                let mut updated = false;
-                
-                for i in 0..lay_levels.len() {
-                    let mut level = lay_levels.get(i);
-                    let mut new_bet_ids = ManagedVec::new();
-                    
-                    for level_bet_id in level.bet_ids.iter() {
-                        if level_bet_id != bet_id {
-                            new_bet_ids.push(level_bet_id);
-                        } else {
-                            updated = true;
-                        }
-                    }
-                    
-                    if new_bet_ids.is_empty() {
-                        // Elimină nivelul complet dacă nu mai conține pariuri
-                        if i < lay_levels.len() - 1 {
-                            let last = lay_levels.get(lay_levels.len() - 1);
-                            let _ = lay_levels.set(i, last);
-                        }
-                        lay_levels.remove(lay_levels.len() - 1);
-                        updated = true;
-                    } else {
-                        level.bet_ids = new_bet_ids;
-                        let _ = lay_levels.set(i, level);
-                    }
-                }
-                
-                if updated {
-                    self.selection_lay_levels(bet.event, bet.selection.id).set(&lay_levels);
-                    
-                    // Recalculează lichiditatea totală
-                    let mut total_liquidity = BigUint::zero();
-                    for level in lay_levels.iter() {
-                        total_liquidity += &level.total_stake;
-                    }
-                    self.selection_lay_liquidity(bet.event, bet.selection.id).set(&total_liquidity);
-                }
-            }
+        if bet.nft_nonce != 0 {
+            self.bet_nonce_to_id(bet.nft_nonce).clear();
+        } else {
+            self.user_open_orders(&bet.bettor, bet.event).swap_remove(&bet_id);
         }
         
-        // 4. Șterge din bet_hash_to_id - Acest pas ar putea fi costisitor, dar este necesar
-        for hash in self.bet_hash_to_id().keys() {
-            let id = self.bet_hash_to_id().get(&hash).unwrap_or_default();
-            if id == bet_id {
-                self.bet_hash_to_id().remove(&hash);
-                break;
-            }
-        }
+        // 3. Șterge din bet_hash_to_id prin maparea inversă
+        let bet_hash = self.bet_id_to_hash(bet_id).take();
+        self.bet_hash_to_id().remove(&bet_hash);
         
-        // 5. În final, șterge bet-ul
+        // 4. În final, șterge bet-ul
         self.bet_by_id(bet_id).clear();
     }
 
+    /// Returns the sport of a market, if it can be found.
+    /// The sport is stored as its 1-based index, so that sport 0 is not read as empty. Markets created
+    /// before it was stored are looked up in the per-event market lists of the registered sports.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn find_market_sport(&self, market_id: u64) -> Option<SportId> {
+        let stored_index = self.market_sport_index(market_id).get();
+        if stored_index > 0 {
+            return Some(stored_index - 1);
+        }
+        if self.markets(market_id).is_empty() {
+            return None;
+        }
 
-}
\ No newline at end of file
+        let event_id = self.markets(market_id).get().event_id;
+        self.sport_ids().iter().find(|sport| {
+            self.markets_by_event_and_sport(*sport, event_id)
+                .get()
+                .iter()
+                .any(|id| id == market_id)
+        })
+    }
+
+    /// Returns the sport of a market, failing if the market does not exist.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn require_market_sport(&self, market_id: u64) -> SportId {
+        match self.find_market_sport(market_id) {
+            Some(sport) => sport,
+            None => sc_panic!(ERR_INVALID_MARKET),
+        }
+    }
+
+    /// Returns the line of a market in tenths. TotalGoals markets created before lines were stored
+    /// have none until migrated, and were offered at the fixed 2.5 goals line.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn resolve_market_line(&self, market_id: u64) -> u64 {
+        let line = self.market_line(market_id).get();
+        if line != NO_LINE || self.market_storage_version(market_id).get() >= STORAGE_VERSION {
+            return line;
+        }
+
+        match self.markets(market_id).get().market_type {
+            MarketType::TotalGoals => TOTAL_GOALS_LINE,
+            _ => line,
+        }
+    }
+
+    /// Returns the score of an event, if set. Scores set before they were keyed by sport are
+    /// read from the legacy per-event entry.
+    /// Parameters:
+    /// - sport: The sport of the event.
+    /// - event_id: The ID of the event.
+    fn find_event_score(&self, sport: SportId, event_id: u64) -> Option<(u32, u32)> {
+        if !self.event_score(sport, event_id).is_empty() {
+            return Some(self.event_score(sport, event_id).get());
+        }
+        if !self.legacy_event_score(event_id).is_empty() {
+            return Some(self.legacy_event_score(event_id).get());
+        }
+        None
+    }
+
+    /// Adds a newly created market to the open-market indexes (per sport and per close-time bucket).
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - sport: The sport of the market.
+    /// - close_timestamp: The timestamp when the market closes.
+    fn index_open_market(&self, market_id: u64, sport: SportId, close_timestamp: u64) {
+        let bucket = close_timestamp / CLOSE_BUCKET_SECONDS;
+        self.open_markets_by_sport(sport).insert(market_id);
+        self.open_markets_by_close_bucket(bucket).insert(market_id);
+        self.open_close_buckets().insert(bucket);
+    }
+
+    /// Removes a closed market from the open-market indexes, dropping its close-time bucket once empty.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    fn unindex_open_market(&self, market_id: u64) {
+        let market = self.markets(market_id).get();
+        let bucket = market.close_timestamp / CLOSE_BUCKET_SECONDS;
+        if let Some(sport) = self.find_market_sport(market_id) {
+            self.open_markets_by_sport(sport).swap_remove(&market_id);
+        }
+
+        let mut bucket_mapper = self.open_markets_by_close_bucket(bucket);
+        bucket_mapper.swap_remove(&market_id);
+        if bucket_mapper.is_empty() {
+            self.open_close_buckets().swap_remove(&bucket);
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::types::{Market, MarketStatus};
+use crate::{
+    constants::constants::BPS_DENOMINATOR,
+    errors::ERR_ODDS_TOO_FAR_FROM_REFERENCE,
+    types::{Market, MarketStatus}
+};
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
@@ -91,10 +95,20 @@ pub trait ValidationModule:
         );
         
         require!(
-            current_timestamp < market.close_timestamp,
+            current_timestamp < market.close_timestamp || self.market_in_play(market_id).get(),
             "Market already closed"
         );
     }
+
+    /// Returns true if bets placed now on the market must wait for the in-play delay before matching.
+    fn is_bet_delay_active(&self, market_id: u64) -> bool {
+        if !self.market_in_play(market_id).get() || self.market_bet_delay(market_id).get() == 0 {
+            return false;
+        }
+
+        let market = self.markets(market_id).get();
+        self.blockchain().get_block_timestamp() >= market.close_timestamp
+    }
     
     fn validate_market_status(&self, market_id: u64) -> bool {
         if self.markets(market_id).is_empty() {
@@ -114,5 +128,28 @@ pub trait ValidationModule:
             
         require!(selection_exists, "Invalid selection");
     }
+
+    /// Rejects odds deviating from the selection's reference odds by more than the market's limit (fat-finger protection).
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - odds: The odds of the order.
+    fn validate_odds_deviation(&self, market_id: u64, selection_id: u64, odds: &BigUint) {
+        let max_deviation_bps = self.max_odds_deviation_bps(market_id).get();
+        if max_deviation_bps == 0 || self.reference_odds(market_id, selection_id).is_empty() {
+            return;
+        }
+
+        let reference = self.reference_odds(market_id, selection_id).get().odds;
+        let deviation = if *odds > reference {
+            odds - &reference
+        } else {
+            &reference - odds
+        };
+        require!(
+            deviation * BigUint::from(BPS_DENOMINATOR) <= reference * BigUint::from(max_deviation_bps),
+            ERR_ODDS_TOO_FAR_FROM_REFERENCE
+        );
+    }
     
 }
\ No newline at end of file
@@ -0,0 +1,401 @@
+use crate::{
+    constants::constants::{BPS_DENOMINATOR, ODDS_PRECISION, VAULT_SHARE_DECIMALS, VAULT_SHARE_TOKEN_NAME, VAULT_SHARE_TOKEN_TICKER},
+    errors::{ERR_INSUFFICIENT_VAULT_LIQUIDITY, ERR_INVALID_MARKET, ERR_INVALID_SHARES, ERR_INVALID_SPREAD, ERR_INVALID_VAULT_TOKEN, ERR_NOTHING_TO_DEPOSIT, ERR_NOT_VAULT_KEEPER, ERR_NO_VAULT_STRATEGY, ERR_PAUSED, ERR_TOKEN_ALREADY_ISSUED, ERR_VAULT_MARKETS_LIVE, ERR_VAULT_SETTLING, ERR_VAULT_TOKEN_LOCKED, ERR_VAULT_TOKEN_NOT_SET, ERR_VAULT_VALUE_LOST},
+    types::{BetStatus, BetType, MarketStatus, Role, VaultStrategy}
+};
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait VaultModule:
+    crate::storage::StorageModule
+    + crate::events::EventsModule
+    + crate::roles::RolesModule
+    + crate::balance::BalanceModule
+    + crate::limits::LimitsModule
+    + crate::nft::NftModule
+    + crate::orderbook::OrderbookModule
+    + crate::validation::ValidationModule
+    + crate::utils::UtilsModule
+    + crate::pause::PauseModule
+    + crate::bet::BetModule
+{
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Vault Setup -----------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Issues the fungible share token representing LP ownership of the vault (admin role).
+    /// Requires an EGLD payment to cover issuance costs.
+    #[payable("EGLD")]
+    #[endpoint(issueVaultShareToken)]
+    fn issue_vault_share_token(&self) {
+        self.require_role(Role::Admin);
+        require!(self.vault_share_token().is_empty(), ERR_TOKEN_ALREADY_ISSUED);
+        let issue_cost = self.call_value().egld_value().clone_value();
+
+        self.vault_share_token().issue_and_set_all_roles(
+            issue_cost,
+            ManagedBuffer::new_from_bytes(VAULT_SHARE_TOKEN_NAME),
+            ManagedBuffer::new_from_bytes(VAULT_SHARE_TOKEN_TICKER),
+            VAULT_SHARE_DECIMALS,
+            Some(VaultModule::callbacks(self).vault_share_issue_callback())
+        );
+    }
+
+    /// Callback function executed after share token issuance to store the token ID.
+    /// Parameters:
+    /// - result: The result of the async call (success or error).
+    #[callback]
+    fn vault_share_issue_callback(
+        &self,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_id) => {
+                self.vault_share_token().set_token_id(token_id);
+            }
+            ManagedAsyncCallResult::Err(_) => { }
+        }
+    }
+
+    /// Sets the token the vault accepts and quotes in (admin role).
+    /// Parameters:
+    /// - token: The vault token.
+    #[endpoint(setVaultToken)]
+    fn set_vault_token(&self, token: EgldOrEsdtTokenIdentifier) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        self.apply_vault_token(&token);
+    }
+
+    /// Sets the address allowed to requote the vault besides the risk manager (admin role).
+    /// Parameters:
+    /// - keeper: The keeper address.
+    #[endpoint(setVaultKeeper)]
+    fn set_vault_keeper(&self, keeper: ManagedAddress) {
+        self.require_role(Role::Admin);
+        self.require_not_timelocked();
+        self.vault_keeper().set(&keeper);
+    }
+
+    /// Configures how the vault quotes a market (risk manager role).
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - spread_bps: The total spread between the back and lay quotes, in basis points.
+    /// - quote_amount: The amount committed to each side of a quote.
+    /// - max_exposure: The maximum amount the vault may commit to the market.
+    #[endpoint(setVaultStrategy)]
+    fn set_vault_strategy(
+        &self,
+        market_id: u64,
+        spread_bps: u64,
+        quote_amount: BigUint,
+        max_exposure: BigUint,
+    ) {
+        self.require_role(Role::RiskManager);
+        self.require_not_timelocked();
+        self.apply_vault_strategy(market_id, &VaultStrategy {
+            spread_bps,
+            quote_amount,
+            max_exposure,
+        });
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- LP Deposits & Withdrawals ---------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Deposits the vault token and mints shares at the current vault value.
+    /// Deposits are rejected while shares exist but the vault value is zero, since those worthless
+    /// shares would otherwise take part of the new deposit.
+    #[payable("*")]
+    #[endpoint(vaultDeposit)]
+    fn vault_deposit(&self) {
+        require!(!self.vault_token().is_empty(), ERR_VAULT_TOKEN_NOT_SET);
+        let caller = self.blockchain().get_caller();
+        let (token, nonce, amount) = self
+            .call_value()
+            .egld_or_single_esdt()
+            .into_tuple();
+        require!(token == self.vault_token().get() && nonce == 0, ERR_INVALID_VAULT_TOKEN);
+        require!(amount > BigUint::zero(), ERR_NOTHING_TO_DEPOSIT);
+        require!(!self.global_paused().get(), ERR_PAUSED);
+        self.require_vault_value_current();
+
+        let share_supply = self.vault_share_supply().get();
+        let vault_value = self.get_vault_value();
+        let shares = if share_supply == BigUint::zero() {
+            amount.clone()
+        } else {
+            require!(vault_value > BigUint::zero(), ERR_VAULT_VALUE_LOST);
+            &amount * &share_supply / &vault_value
+        };
+        require!(shares > BigUint::zero(), ERR_INVALID_SHARES);
+
+        let vault_address = self.blockchain().get_sc_address();
+        self.credit_balance(&vault_address, &token, &amount);
+
+        self.vault_share_supply().update(|supply| *supply += &shares);
+        self.vault_share_token().mint_and_send(&caller, shares.clone());
+
+        self.vault_deposit_event(&caller, &amount, &shares);
+    }
+
+    /// Burns the attached shares and pays out their part of the vault value.
+    /// Only the vault's free liquidity can be withdrawn; funds committed to orders stay in the markets.
+    #[payable("*")]
+    #[endpoint(vaultWithdraw)]
+    fn vault_withdraw(&self) {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        self.vault_share_token().require_same_token(&payment.token_identifier);
+        self.require_vault_value_current();
+
+        let shares = payment.amount;
+        let share_supply = self.vault_share_supply().get();
+        require!(shares > BigUint::zero() && shares <= share_supply, ERR_INVALID_SHARES);
+
+        let amount = &shares * &self.get_vault_value() / &share_supply;
+        let token = self.vault_token().get();
+        let vault_address = self.blockchain().get_sc_address();
+        require!(
+            self.claimable_balance(&vault_address, &token).get() >= amount,
+            ERR_INSUFFICIENT_VAULT_LIQUIDITY
+        );
+
+        self.debit_balance(&vault_address, &token, &amount);
+        self.vault_share_supply().update(|supply| *supply -= &shares);
+        self.vault_share_token().burn(&shares);
+
+        self.send().direct(&caller, &token, 0, &amount);
+        self.vault_withdraw_event(&caller, &amount, &shares);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Quoting Strategy ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Replaces the vault's quotes on a selection with a back and a lay order around a reference price
+    /// (risk manager or vault keeper). The vault's previous unmatched orders on the selection are cancelled
+    /// first, and new orders are capped by the market's max exposure and the vault's free liquidity.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - reference_odds: The fair odds to quote around (e.g., 250 for 2.50).
+    #[endpoint(vaultQuote)]
+    fn vault_quote(&self, market_id: u64, selection_id: u64, reference_odds: BigUint) {
+        self.require_vault_keeper();
+        require!(!self.vault_strategy(market_id).is_empty(), ERR_NO_VAULT_STRATEGY);
+        require!(!self.vault_token().is_empty(), ERR_VAULT_TOKEN_NOT_SET);
+
+        self.cancel_vault_quotes(market_id, selection_id);
+
+        let strategy = self.vault_strategy(market_id).get();
+        let half_spread = BigUint::from(strategy.spread_bps / 2);
+        let bps = BigUint::from(BPS_DENOMINATOR);
+        let back_odds = &reference_odds * &(&bps + &half_spread) / &bps;
+        let lay_odds = &reference_odds * &(&bps - &half_spread) / &bps;
+
+        self.place_vault_order(market_id, selection_id, &back_odds, BetType::Back, &strategy);
+        if lay_odds > ODDS_PRECISION {
+            self.place_vault_order(market_id, selection_id, &lay_odds, BetType::Lay, &strategy);
+        }
+
+        self.vault_markets().insert(market_id);
+        self.vault_quoted_event(market_id, selection_id, &reference_odds, &back_odds, &lay_odds);
+    }
+
+    /// Cancels the vault's unmatched orders on a selection (risk manager or vault keeper).
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    #[endpoint(vaultCancelQuotes)]
+    fn vault_cancel_quotes(&self, market_id: u64, selection_id: u64) {
+        self.require_vault_keeper();
+        self.cancel_vault_quotes(market_id, selection_id);
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Helper Functions ------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Sets the vault token, directly or through an executed proposal. Fails while shares are outstanding.
+    /// Parameters:
+    /// - token: The vault token.
+    fn apply_vault_token(&self, token: &EgldOrEsdtTokenIdentifier) {
+        require!(self.vault_share_supply().get() == BigUint::zero(), ERR_VAULT_TOKEN_LOCKED);
+        self.vault_token().set(token);
+    }
+
+    /// Stores the quoting strategy of a market, directly or through an executed proposal.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - strategy: The spread, quote amount and exposure cap.
+    fn apply_vault_strategy(&self, market_id: u64, strategy: &VaultStrategy<Self::Api>) {
+        require!(!self.markets(market_id).is_empty(), ERR_INVALID_MARKET);
+        require!(strategy.spread_bps < BPS_DENOMINATOR, ERR_INVALID_SPREAD);
+        self.vault_strategy(market_id).set(strategy);
+    }
+
+    /// Places one vault order funded from the vault's free liquidity, within the market's exposure cap.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    /// - odds: The odds to quote.
+    /// - bet_type: The side of the quote.
+    /// - strategy: The market's vault strategy.
+    fn place_vault_order(
+        &self,
+        market_id: u64,
+        selection_id: u64,
+        odds: &BigUint,
+        bet_type: BetType,
+        strategy: &VaultStrategy<Self::Api>,
+    ) {
+        let vault_address = self.blockchain().get_sc_address();
+        let token = self.vault_token().get();
+        let exposure = self.vault_market_exposure(market_id).get();
+        if exposure >= strategy.max_exposure {
+            return;
+        }
+
+        let mut amount = strategy.quote_amount.clone();
+        let exposure_left = &strategy.max_exposure - &exposure;
+        if amount > exposure_left {
+            amount = exposure_left;
+        }
+        let free_liquidity = self.claimable_balance(&vault_address, &token).get();
+        if amount > free_liquidity {
+            amount = free_liquidity;
+        }
+        if amount == BigUint::zero() {
+            return;
+        }
+
+        self.debit_balance(&vault_address, &token, &amount);
+        self.vault_market_exposure(market_id).update(|val| *val += &amount);
+
+        let sport = self.require_market_sport(market_id);
+        self.execute_bet(
+            &vault_address,
+            sport,
+            market_id,
+            selection_id,
+            odds.clone(),
+            bet_type,
+            token,
+            0,
+            amount,
+            false,
+            0
+        );
+    }
+
+    /// Cancels the vault's unmatched orders on a selection and releases their exposure.
+    /// Parameters:
+    /// - market_id: The ID of the market.
+    /// - selection_id: The ID of the selection.
+    fn cancel_vault_quotes(&self, market_id: u64, selection_id: u64) {
+        let vault_address = self.blockchain().get_sc_address();
+        let bet_ids = self.user_open_orders(&vault_address, market_id).iter().collect::<ManagedVec<u64>>();
+
+        for bet_id in bet_ids.iter() {
+            if self.bet_by_id(bet_id).is_empty() {
+                continue;
+            }
+
+            let bet = self.bet_by_id(bet_id).get();
+            if bet.selection.id != selection_id {
+                continue;
+            }
+            if bet.status != BetStatus::Unmatched
+                && bet.status != BetStatus::PartiallyMatched
+                && bet.status != BetStatus::Pending
+            {
+                continue;
+            }
+
+            let refund = self.cancel_unmatched_part(bet);
+            self.vault_market_exposure(market_id).update(|val| {
+                if *val >= refund {
+                    *val -= &refund;
+                } else {
+                    *val = BigUint::zero();
+                }
+            });
+        }
+    }
+
+    /// Fails unless the caller is a risk manager or the vault keeper.
+    fn require_vault_keeper(&self) {
+        let caller = self.blockchain().get_caller();
+        let is_keeper = !self.vault_keeper().is_empty() && self.vault_keeper().get() == caller;
+        require!(
+            self.has_role_internal(Role::RiskManager, &caller) || is_keeper,
+            ERR_NOT_VAULT_KEEPER
+        );
+    }
+
+    /// Drops settled markets from the vault and fails while the vault value cannot be trusted:
+    /// while any vault market is closed but not yet settled, since refunds and payouts credited during
+    /// settlement would otherwise be counted twice, and while any vault market is suspended or past its
+    /// close time, in play or not yet closed, since its open positions are valued at cost and LPs could
+    /// enter or exit around swings not yet realized.
+    fn require_vault_value_current(&self) {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let market_ids = self.vault_markets().iter().collect::<ManagedVec<u64>>();
+        for market_id in market_ids.iter() {
+            let market = self.markets(market_id).get();
+            let status = market.market_status;
+            if status == MarketStatus::Settled {
+                self.vault_markets().swap_remove(&market_id);
+                self.vault_market_exposure(market_id).clear();
+                continue;
+            }
+
+            require!(status != MarketStatus::Closed, ERR_VAULT_SETTLING);
+            let live = status == MarketStatus::Suspended || current_timestamp >= market.close_timestamp;
+            require!(!live, ERR_VAULT_MARKETS_LIVE);
+        }
+    }
+
+    //--------------------------------------------------------------------------------------------//
+    //-------------------------------- Views -----------------------------------------------------//
+    //--------------------------------------------------------------------------------------------//
+
+    /// Returns the vault value: its free liquidity plus the funds committed to markets not yet settled.
+    #[view(getVaultValue)]
+    fn get_vault_value(&self) -> BigUint {
+        if self.vault_token().is_empty() {
+            return BigUint::zero();
+        }
+
+        let vault_address = self.blockchain().get_sc_address();
+        let mut value = self.claimable_balance(&vault_address, &self.vault_token().get()).get();
+        for market_id in self.vault_markets().iter() {
+            if self.markets(market_id).get().market_status != MarketStatus::Settled {
+                value += self.vault_market_exposure(market_id).get();
+            }
+        }
+        value
+    }
+
+    #[view(getVaultShareSupply)]
+    fn get_vault_share_supply(&self) -> BigUint {
+        self.vault_share_supply().get()
+    }
+
+    #[view(getVaultStrategy)]
+    fn get_vault_strategy(&self, market_id: u64) -> OptionalValue<VaultStrategy<Self::Api>> {
+        if self.vault_strategy(market_id).is_empty() {
+            OptionalValue::None
+        } else {
+            OptionalValue::Some(self.vault_strategy(market_id).get())
+        }
+    }
+
+    #[view(getVaultMarketExposure)]
+    fn get_vault_market_exposure(&self, market_id: u64) -> BigUint {
+        self.vault_market_exposure(market_id).get()
+    }
+}
@@ -0,0 +1,146 @@
+use multiversx_sc::types::BigUint;
+use multiversx_sc_scenario::api::StaticApi;
+use rockstake::{
+    escrow::{locked_share, mul_div_ceil, mul_div_floor, refund_share, split_unmatched},
+    types::BetType,
+};
+
+fn big(value: u128) -> BigUint<StaticApi> {
+    BigUint::from(value)
+}
+
+#[test]
+fn mul_div_rounds_in_both_directions() {
+    assert_eq!(mul_div_floor(&big(1000), &big(100), &big(333)), big(300));
+    assert_eq!(mul_div_ceil(&big(1000), &big(100), &big(333)), big(301));
+
+    assert_eq!(mul_div_floor(&big(3000), &big(5), &big(1000)), big(15));
+    assert_eq!(mul_div_ceil(&big(3000), &big(5), &big(1000)), big(15));
+
+    assert_eq!(mul_div_floor(&big(1), &big(1), &big(2)), big(0));
+    assert_eq!(mul_div_ceil(&big(1), &big(1), &big(2)), big(1));
+    assert_eq!(mul_div_ceil(&big(0), &big(7), &big(3)), big(0));
+}
+
+#[test]
+fn back_shares_are_the_part_itself() {
+    let total = big(1000);
+    assert_eq!(locked_share(BetType::Back, &total, &total, &big(7)), big(7));
+    assert_eq!(refund_share(BetType::Back, &total, &total, &big(7)), big(7));
+
+    let (refund, retained, dust) = split_unmatched(BetType::Back, &total, &total, &big(7));
+    assert_eq!(refund, big(7));
+    assert_eq!(retained, big(993));
+    assert_eq!(dust, big(0));
+}
+
+#[test]
+fn lay_refund_keeps_sub_percent_remainders() {
+    // 3000 escrowed at odds 3.00 for a stake of 1000, 0.5% left unmatched.
+    // A whole-percent ratio would refund nothing here.
+    let total = big(3000);
+    let stake = big(1000);
+    assert_eq!(refund_share(BetType::Lay, &total, &stake, &big(5)), big(15));
+    assert_eq!(locked_share(BetType::Lay, &total, &stake, &big(5)), big(15));
+}
+
+#[test]
+fn lay_split_rounds_in_protocol_favor() {
+    let total = big(1000);
+    let stake = big(333);
+    let (refund, retained, dust) = split_unmatched(BetType::Lay, &total, &stake, &big(100));
+
+    // The exact share is 300.3: the bettor gets 300, the matched part keeps 699, the protocol 1.
+    assert_eq!(refund, big(300));
+    assert_eq!(retained, big(699));
+    assert_eq!(dust, big(1));
+    assert_eq!(&refund + &retained + &dust, total);
+    assert!(retained >= big(233));
+}
+
+#[test]
+fn fully_unmatched_lay_is_refunded_in_full() {
+    let total = big(1000);
+    let stake = big(333);
+    let (refund, retained, dust) = split_unmatched(BetType::Lay, &total, &stake, &stake);
+    assert_eq!(refund, total);
+    assert_eq!(retained, big(0));
+    assert_eq!(dust, big(0));
+}
+
+#[test]
+fn one_wei_stakes() {
+    // 2 escrowed at odds 2.00 and 3 at odds 2.50 both give a stake of 1.
+    assert_eq!(locked_share(BetType::Lay, &big(2), &big(1), &big(1)), big(2));
+    assert_eq!(refund_share(BetType::Lay, &big(3), &big(1), &big(1)), big(3));
+    assert_eq!(refund_share(BetType::Lay, &big(3), &big(1), &big(0)), big(0));
+    assert_eq!(locked_share(BetType::Back, &big(1), &big(1), &big(1)), big(1));
+}
+
+#[test]
+fn large_stakes_do_not_truncate() {
+    let unit = 10u128.pow(30);
+    let total = big(7 * unit);
+    let stake = big(3 * unit);
+    let part = big(unit);
+
+    let floor = 7 * unit / 3;
+    assert_eq!(refund_share(BetType::Lay, &total, &stake, &part), big(floor));
+    assert_eq!(locked_share(BetType::Lay, &total, &stake, &part), big(floor + 1));
+
+    let (refund, retained, dust) = split_unmatched(BetType::Lay, &total, &stake, &part);
+    assert_eq!(&refund + &retained + &dust, total);
+    assert_eq!(dust, big(1));
+}
+
+#[test]
+fn high_odds_lay() {
+    // 1_000_000 escrowed at odds 1000.00 for a stake of 1000.
+    let total = big(1_000_000);
+    assert_eq!(refund_share(BetType::Lay, &total, &big(1000), &big(1)), big(1000));
+
+    let (refund, retained, dust) = split_unmatched(BetType::Lay, &total, &big(1001), &big(1));
+    assert_eq!(refund, big(999));
+    assert_eq!(dust, big(1));
+    assert_eq!(retained, big(999_000));
+}
+
+#[test]
+fn lowest_odds_lay() {
+    // 101 escrowed at odds 1.01 for a stake of 100: each unit of stake carries 1.01.
+    let total = big(101);
+    let stake = big(100);
+    assert_eq!(refund_share(BetType::Lay, &total, &stake, &big(50)), big(50));
+    assert_eq!(locked_share(BetType::Lay, &total, &stake, &big(50)), big(51));
+    assert_eq!(locked_share(BetType::Lay, &total, &stake, &big(100)), big(101));
+}
+
+#[test]
+fn releases_add_up_to_the_initial_lock() {
+    let total = big(1000);
+    let stake = big(333);
+    let initial = locked_share(BetType::Lay, &total, &stake, &stake);
+
+    let mut unmatched = 333u128;
+    let mut released = big(0);
+    for step in [100u128, 100, 133] {
+        let before = locked_share(BetType::Lay, &total, &stake, &big(unmatched));
+        unmatched -= step;
+        let after = locked_share(BetType::Lay, &total, &stake, &big(unmatched));
+        released += before - after;
+    }
+    assert_eq!(released, initial);
+    assert_eq!(initial, total);
+}
+
+#[test]
+fn floor_never_exceeds_ceil() {
+    let total = big(987_654_321);
+    let stake = big(123_457);
+    for part in [1u128, 2, 999, 61_728, 123_456, 123_457] {
+        let floor = refund_share(BetType::Lay, &total, &stake, &big(part));
+        let ceil = locked_share(BetType::Lay, &total, &stake, &big(part));
+        assert!(floor <= ceil);
+        assert!(&ceil - &floor <= big(1));
+    }
+}